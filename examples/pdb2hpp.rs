@@ -134,13 +134,13 @@ impl<'p> Class<'p> {
                 // TODO: attributes (static, virtual, etc.)
                 self.fields.push(Field{
                     type_name: type_name(type_finder, data.field_type, needed_types)?,
-                    name: data.name.clone(),
+                    name: data.name,
                     offset: data.offset,
                 });
             },
 
             &pdb::TypeData::Method(ref data) => {
-                let method = Method::find(data.name.clone(), data.attributes, type_finder, data.method_type, needed_types)?;
+                let method = Method::find(data.name, data.attributes, type_finder, data.method_type, needed_types)?;
                 if data.attributes.is_static() {
                     self.static_methods.push(method);
                 } else {
@@ -156,7 +156,7 @@ impl<'p> Class<'p> {
                         let mut iter = method_list.methods.into_iter();
                         while let Some(pdb::MethodListEntry { attributes, method_type, .. }) = iter.next() {
                             // hooray
-                            let method = Method::find(data.name.clone(), attributes, type_finder, method_type, needed_types)?;
+                            let method = Method::find(data.name, attributes, type_finder, method_type, needed_types)?;
                             if attributes.is_static() {
                                 self.static_methods.push(method);
                             } else {
@@ -345,7 +345,7 @@ impl<'p> Enum<'p> {
         match field {
             &pdb::TypeData::Enumerate(ref data) => {
                 self.values.push(EnumValue{
-                    name: data.name.clone(),
+                    name: data.name,
                     value: data.value,
                 });
             },
@@ -0,0 +1,175 @@
+#!/usr/bin/env python3
+#
+# Writes cross_module.pdb, a minimal PDB exercising features which the Visual Studio fixture in
+# fixtures/self does not use. See README.md for its contents.
+
+import struct
+
+PAGE_SIZE = 0x200
+MSF_MAGIC = b"Microsoft C/C++ MSF 7.00\r\n\x1a\x44\x53\x00\x00\x00"
+
+
+def u16(value):
+    return struct.pack("<H", value)
+
+
+def u32(value):
+    return struct.pack("<I", value)
+
+
+def pad(data, alignment=4):
+    return data + b"\0" * (-len(data) % alignment)
+
+
+def hash_v1(data):
+    """LHashPbCb, as implemented by StringTable::find()."""
+    result = 0
+    for i in range(0, len(data) - len(data) % 4, 4):
+        result ^= struct.unpack_from("<I", data, i)[0]
+    rest = data[len(data) - len(data) % 4:]
+    if len(rest) >= 2:
+        result ^= struct.unpack_from("<H", rest)[0]
+        rest = rest[2:]
+    if rest:
+        result ^= rest[0]
+    result |= 0x20202020
+    result ^= result >> 11
+    return result ^ (result >> 16)
+
+
+def string_table(names):
+    data = b"\0"
+    offsets = {}
+    for name in names:
+        offsets[name] = len(data)
+        data += name.encode() + b"\0"
+
+    buckets = [0] * (len(names) * 2)
+    for name in names:
+        index = hash_v1(name.encode()) % len(buckets)
+        while buckets[index] != 0:
+            index = (index + 1) % len(buckets)
+        buckets[index] = offsets[name]
+
+    stream = u32(0xeffeeffe) + u32(1) + u32(len(data)) + data
+    stream += u32(len(buckets)) + b"".join(u32(b) for b in buckets) + u32(len(names))
+    return stream, offsets
+
+
+def pdb_info(named_streams):
+    names = b""
+    entries = b""
+    for name, stream in named_streams:
+        entries += u32(len(names)) + u32(stream)
+        names += name.encode() + b"\0"
+
+    count = len(named_streams)
+    stream = u32(20000404) + u32(0x5c0ffee5) + u32(1) + bytes(range(16))
+    stream += u32(len(names)) + names
+    stream += u32(count) + u32(count) + u32(1) + u32((1 << count) - 1) + u32(0) + entries
+    return stream
+
+
+def section_header(name, virtual_address, virtual_size, characteristics):
+    return (name.encode().ljust(8, b"\0") + u32(virtual_size) + u32(virtual_address) +
+            u32(virtual_size) + u32(0x400) + u32(0) + u32(0) + u16(0) + u16(0) + u32(characteristics))
+
+
+def subsection(kind, data):
+    return u32(kind) + u32(len(data)) + pad(data)
+
+
+def module_stream(subsections):
+    # the symbols only consist of the signature
+    c13 = b"".join(subsection(kind, data) for kind, data in subsections)
+    return u32(4) + c13, 4, len(c13)
+
+
+def module_record(name, stream, symbols_size, c13_size):
+    record = u32(0)
+    # section contribution
+    record += u16(0xffff) + u16(0) + u32(0) + u32(0) + u32(0) + u16(0xffff) + u16(0) + u32(0) + u32(0)
+    record += u16(0) + u16(stream) + u32(symbols_size) + u32(0) + u32(c13_size)
+    record += u16(0) + u16(0) + u32(0) + u32(0) + u32(0)
+    return pad(record + name.encode() + b"\0" + name.encode() + b"\0")
+
+
+def dbi(modules, section_headers_stream):
+    module_list = b"".join(modules)
+    # FPO, exception, fixup, OMAP to/from source, section headers, token RID map, xdata, pdata,
+    # frame data, original section headers
+    extra = [0xffff] * 11
+    extra[5] = section_headers_stream
+    debug_header = b"".join(u16(s) for s in extra)
+
+    header = u32(0xffffffff) + u32(19990903) + u32(1)
+    header += u16(0xffff) + u16(0) + u16(0xffff) + u16(0) + u16(0xffff) + u16(0)
+    header += u32(len(module_list)) + u32(0) + u32(0) + u32(0) + u32(0) + u32(0)
+    header += u32(len(debug_header)) + u32(0) + u16(0) + u16(0x8664) + u32(0)
+    return header + module_list + debug_header
+
+
+def msf(streams):
+    pages = [b""] * 3
+    def allocate(data):
+        numbers = []
+        for i in range(0, len(data), PAGE_SIZE):
+            numbers.append(len(pages))
+            pages.append(data[i:i + PAGE_SIZE].ljust(PAGE_SIZE, b"\0"))
+        return numbers
+
+    directory = u32(len(streams)) + b"".join(u32(len(s)) for s in streams)
+    for stream in streams:
+        directory += b"".join(u32(n) for n in allocate(stream))
+
+    directory_pages = allocate(directory)
+    directory_page_list = allocate(b"".join(u32(n) for n in directory_pages))
+
+    header = MSF_MAGIC + u32(PAGE_SIZE) + u32(1) + u32(len(pages)) + u32(len(directory)) + u32(0)
+    header += b"".join(u32(n) for n in directory_page_list)
+    pages[0] = header.ljust(PAGE_SIZE, b"\0")
+    pages[1] = b"\0" * PAGE_SIZE
+    pages[2] = b"\0" * PAGE_SIZE
+    return b"".join(pages)
+
+
+def main():
+    strings, offsets = string_table(["a.cpp", "b.obj"])
+
+    # a.obj: one line at 1:0010, and an import of item 0x1003 from b.obj
+    # the file has no checksum
+    checksums = pad(u32(offsets["a.cpp"]) + bytes([0, 0]))
+    lines = u32(0x10) + u16(1) + u16(0) + u32(0x20)
+    lines += u32(0) + u32(1) + u32(12 + 8) + u32(0) + u32(0x80000000 | 7)
+    imports = u32(offsets["b.obj"]) + u32(1) + u32(0x1003)
+    a, a_symbols, a_c13 = module_stream([(0xf2, lines), (0xf4, checksums), (0xf7, imports)])
+
+    # b.obj: exports its item 0x1003 as global item 0x1234
+    exports = u32(0x1003) + u32(0x1234)
+    b, b_symbols, b_c13 = module_stream([(0xf8, exports)])
+
+    modules = [
+        module_record("a.obj", 7, a_symbols, a_c13),
+        module_record("b.obj", 8, b_symbols, b_c13),
+        # linker-generated modules usually have no module info stream
+        module_record("* Linker *", 0xffff, 0, 0),
+    ]
+
+    streams = [
+        b"",
+        pdb_info([("/names", 5)]),
+        b"",
+        dbi(modules, 6),
+        b"",
+        strings,
+        section_header(".text", 0x1000, 0x100, 0x60000020),
+        a,
+        b,
+    ]
+
+    with open("cross_module.pdb", "wb") as f:
+        f.write(msf(streams))
+
+
+if __name__ == "__main__":
+    main()
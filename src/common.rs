@@ -18,6 +18,17 @@ use scroll::ctx::TryFromCtx;
 /// `TypeIndex` refers to a type somewhere in `PDB.type_information()`.
 pub type TypeIndex = u32;
 
+/// `ItemIndex` refers to an item, such as an `LF_FUNC_ID` record, in the IPI stream.
+///
+/// The IPI stream has the same format as the type information stream, but holds records which
+/// describe functions and build information rather than types.
+pub type ItemIndex = u32;
+
+/// `FileIndex` refers to a source file in the line information of a module.
+///
+/// Resolve it to a name with `ModuleInfo::file_name()`.
+pub type FileIndex = u32;
+
 /// An error that occurred while reading or parsing the PDB.
 #[derive(Debug)]
 pub enum Error {
@@ -71,6 +82,24 @@ pub enum Error {
 
     /// A parse error from scroll.
     ScrollError(scroll::Error),
+
+    /// The requested stream name is not present in the PDB information stream.
+    StreamNameNotFound,
+
+    /// The string table stream was invalid.
+    InvalidStringTable(&'static str),
+
+    /// A frame program could not be evaluated.
+    InvalidFrameProgram(&'static str),
+
+    /// A frame program needed to read memory at this address, but the memory was not available.
+    MemoryReadFailed(u32),
+
+    /// A symbol of a different kind was expected here.
+    UnexpectedSymbolKind(u16),
+
+    /// The binary annotations of an inline site were invalid.
+    InvalidBinaryAnnotation(&'static str),
 }
 
 impl error::Error for Error {
@@ -92,6 +121,12 @@ impl error::Error for Error {
             Error::UnimplementedTypeKind(_) => "Support for types of this kind is not implemented",
             Error::UnexpectedNumericPrefix(_) => "Variable-length numeric parsing encountered an unexpected prefix",
             Error::ScrollError(ref e) => e.description(),
+            Error::StreamNameNotFound => "The requested stream name is not present in this file",
+            Error::InvalidStringTable(_) => "The string table was invalid",
+            Error::InvalidFrameProgram(_) => "A frame program could not be evaluated",
+            Error::MemoryReadFailed(_) => "A frame program read from unavailable memory",
+            Error::UnexpectedSymbolKind(_) => "A symbol of a different kind was expected here",
+            Error::InvalidBinaryAnnotation(_) => "The binary annotations of an inline site were invalid",
         }
     }
 }
@@ -110,6 +145,11 @@ impl fmt::Display for Error {
             Error::TypeNotIndexed(type_index, indexed_count) => write!(f, "Type {} not indexed (index covers {})", type_index, indexed_count),
             Error::UnimplementedTypeKind(kind) => write!(f, "Support for types of kind 0x{:04x} is not implemented", kind),
             Error::UnexpectedNumericPrefix(prefix) => write!(f, "Variable-length numeric parsing encountered an unexpected prefix (0x{:04x}", prefix),
+            Error::InvalidStringTable(reason) => write!(f, "The string table was invalid: {}", reason),
+            Error::InvalidFrameProgram(reason) => write!(f, "A frame program could not be evaluated: {}", reason),
+            Error::MemoryReadFailed(address) => write!(f, "A frame program read from unavailable memory (0x{:08x})", address),
+            Error::UnexpectedSymbolKind(kind) => write!(f, "A symbol of a different kind was expected here, not 0x{:04x}", kind),
+            Error::InvalidBinaryAnnotation(reason) => write!(f, "The binary annotations of an inline site were invalid: {}", reason),
             _ => fmt::Debug::fmt(self, f)
         }
     }
@@ -179,7 +219,7 @@ impl<'b> ParseBuffer<'b> {
     pub fn align(&mut self, alignment: usize) -> Result<()> {
         let diff = self.1 % alignment;
         if diff > 0 {
-            if self.len() < diff {
+            if self.len() < alignment - diff {
                 return Err(Error::UnexpectedEof);
             }
             self.1 += alignment - diff;
@@ -298,7 +338,7 @@ pub enum Variant {
 /// `RawString` refers to a `&[u8]` that physically resides somewhere inside a PDB data structure.
 ///
 /// A `RawString` may not be valid UTF-8.
-#[derive(Clone,PartialEq,Eq,Hash,PartialOrd,Ord)]
+#[derive(Copy,Clone,PartialEq,Eq,Hash,PartialOrd,Ord)]
 pub struct RawString<'b>(&'b [u8]);
 
 impl<'b> fmt::Debug for RawString<'b> {
@@ -564,5 +604,31 @@ mod tests {
                 _ => panic!("expected EOF")
             }
         }
+
+        #[test]
+        fn test_align() {
+            let vec: Vec<u8> = vec![1, 2, 3, 4, 5, 6, 7, 8];
+            let mut buf = ParseBuffer::from(vec.as_slice());
+
+            buf.align(4).expect("aligned already");
+            assert_eq!(buf.pos(), 0);
+
+            buf.parse_u8().unwrap();
+            buf.align(4).expect("align");
+            assert_eq!(buf.pos(), 4);
+
+            // the padding may run up to the very end of the buffer
+            buf.take(3).unwrap();
+            buf.align(4).expect("align to end");
+            assert_eq!(buf.pos(), 8);
+            assert_eq!(buf.len(), 0);
+
+            let mut buf = ParseBuffer::from(&vec[..6]);
+            buf.take(5).unwrap();
+            match buf.align(4) {
+                Err(Error::UnexpectedEof) => (),
+                _ => panic!("expected EOF")
+            }
+        }
     }
 }
@@ -8,15 +8,19 @@
 // DBI = "Debug Information"
 
 use std::borrow::Cow;
+use std::cmp;
 use std::result;
 
 use common::*;
 use msf::*;
+use pe::ImageSectionHeader;
 use FallibleIterator;
 
 /// Provides access to the "DBI" stream inside the PDB.
 ///
-/// This is only minimally implemented; it's really just so `PDB` can find the global symbol table.
+/// The DBI stream describes the modules which make up the binary, which parts of which sections
+/// they contributed, and the logical segments used by `segment:offset` addresses. It also tells
+/// `PDB` where to find the global symbol table.
 ///
 /// # Example
 ///
@@ -57,6 +61,145 @@ impl<'s> DebugInformation<'s> {
         let modules_buf = buf.take(self.header.module_list_size as usize)?;
         Ok(ModuleIter { buf: modules_buf.into() })
     }
+
+    /// Returns an iterator that can traverse the section contributions list in sequential order.
+    ///
+    /// Each `DBISectionContribution` describes a range of a section that was contributed by a
+    /// single module, which makes it possible to map a `segment:offset` address back to its
+    /// `Module`. The list is sorted by section and offset.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::UnimplementedFeature` if the section contributions substream is an unsupported
+    ///   version
+    pub fn section_contributions(&self) -> Result<SectionContributionIter<'_>> {
+        let mut buf = self.stream.parse_buffer();
+        // drop the header and the modules list
+        buf.take(self.header_len + self.header.module_list_size as usize)?;
+        let contributions_buf = buf.take(self.header.section_contribution_size as usize)?;
+        SectionContributionIter::parse(contributions_buf.into())
+    }
+
+    /// Returns an iterator that can traverse the section map in sequential order.
+    ///
+    /// The section map describes the logical segments referred to by `segment:offset` addresses
+    /// throughout the PDB, such as `PublicSymbol::segment`. Segment numbers are 1-based, so the
+    /// first `SectionMapEntry` describes segment 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pdb::FallibleIterator;
+    /// #
+    /// # fn test() -> pdb::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb::PDB::open(file)?;
+    /// let dbi = pdb.debug_information()?;
+    ///
+    /// let mut section_map = dbi.section_map()?;
+    /// let mut segment = 1;
+    /// while let Some(entry) = section_map.next()? {
+    ///     println!("segment {} is frame {} at offset {:x} ({} bytes)",
+    ///              segment, entry.frame, entry.offset, entry.section_length);
+    ///     segment += 1;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn section_map(&self) -> Result<SectionMapIter<'_>> {
+        let mut buf = self.stream.parse_buffer();
+        // drop the header, the modules list, and the section contributions
+        buf.take(self.header_len + self.header.module_list_size as usize +
+                 self.header.section_contribution_size as usize)?;
+        let section_map_buf = buf.take(self.header.section_map_size as usize)?;
+        SectionMapIter::parse(section_map_buf.into())
+    }
+
+    /// Returns the file info substream, which lists the source files contributing to each
+    /// module.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pdb::FallibleIterator;
+    /// #
+    /// # fn test() -> pdb::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb::PDB::open(file)?;
+    /// let dbi = pdb.debug_information()?;
+    ///
+    /// let file_info = dbi.file_info()?;
+    /// let mut modules = dbi.modules()?;
+    /// let mut files = file_info.iter();
+    /// while let (Some(module), Some(mut module_files)) = (modules.next()?, files.next()?) {
+    ///     println!("{}:", module.module_name());
+    ///     while let Some(file_name) = module_files.next()? {
+    ///         println!("  - {}", file_name);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn file_info(&self) -> Result<FileInfo<'_>> {
+        let mut buf = self.stream.parse_buffer();
+        // drop the header, the modules list, the section contributions, and the section map
+        buf.take(self.header_len + self.header.module_list_size as usize +
+                 self.header.section_contribution_size as usize +
+                 self.header.section_map_size as usize)?;
+        let file_info_buf = buf.take(self.header.file_info_size as usize)?;
+        FileInfo::parse(file_info_buf.into())
+    }
+
+    /// Returns the stream numbers of the optional debug streams referenced by this DBI stream.
+    ///
+    /// These streams hold data copied from the executable by the linker, such as FPO records,
+    /// section headers, and OMAP tables. `PDB::extra_stream()` opens them by role.
+    pub fn extra_streams(&self) -> Result<DBIExtraStreams> {
+        let mut buf = self.stream.parse_buffer();
+        // drop the header and every substream preceding the debug header
+        buf.take(self.header_len + self.header.module_list_size as usize +
+                 self.header.section_contribution_size as usize +
+                 self.header.section_map_size as usize +
+                 self.header.file_info_size as usize +
+                 self.header.type_server_map_size as usize +
+                 self.header.ec_substream_size as usize)?;
+        let debug_header_buf = buf.take(self.header.debug_header_size as usize)?;
+        DBIExtraStreams::parse(debug_header_buf.into())
+    }
+
+    /// Builds a `ContributionIndex` which finds the `Module` owning a given `segment:offset`
+    /// address.
+    ///
+    /// This reads the entire modules list and section contributions substream into memory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn test() -> pdb::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb::PDB::open(file)?;
+    /// let dbi = pdb.debug_information()?;
+    ///
+    /// let index = dbi.contribution_index()?;
+    /// if let Some((module, contribution)) = index.find(1, 0x55c0) {
+    ///     println!("{} contributed {} bytes at {:x}:{:08x}", module.module_name(),
+    ///              contribution.size, contribution.section, contribution.offset);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn contribution_index(&self) -> Result<ContributionIndex<'_>> {
+        let modules = self.modules()?.collect()?;
+        let mut contributions: Vec<DBISectionContribution> = self.section_contributions()?
+            .filter(|c| c.size > 0)
+            .collect()?;
+
+        // the linker emits these in order, but don't count on it
+        contributions.sort_by_key(|c| (c.section, c.offset));
+        let ends = contribution_ends(&contributions);
+
+        Ok(ContributionIndex { modules, contributions, ends })
+    }
 }
 
 pub fn new_debug_information(stream: Stream) -> Result<DebugInformation> {
@@ -201,29 +344,148 @@ pub fn parse_header(buf: &mut ParseBuffer) -> Result<Header> {
     Ok(header)
 }
 
+/// Identifies one of the optional debug streams described by `DBIExtraStreams`.
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub enum ExtraStreamKind {
+    /// Frame pointer omission records (`FPO_DATA`).
+    Fpo,
+    /// Exception handling data.
+    Exception,
+    /// Fixup records.
+    Fixup,
+    /// OMAP records mapping RVAs in the transformed (released) image to RVAs in the original
+    /// image, which symbols refer to.
+    OmapToSource,
+    /// OMAP records mapping RVAs in the original image to RVAs in the transformed image.
+    OmapFromSource,
+    /// The section headers of the executable (`IMAGE_SECTION_HEADER`).
+    SectionHeaders,
+    /// A map of CLR metadata tokens to record identifiers.
+    TokenRidMap,
+    /// A copy of the `.xdata` section.
+    Xdata,
+    /// A copy of the `.pdata` section.
+    Pdata,
+    /// New-style frame data records (`FRAMEDATA`), also called "new FPO".
+    FrameData,
+    /// The section headers of the executable before it was transformed by a tool like BBT.
+    OriginalSectionHeaders,
+}
+
+/// The stream numbers of the optional debug streams, as described by the debug header substream
+/// at the end of the DBI stream. Named `DbgDataHdr` in the Microsoft PDB source:
+/// https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/PDB/dbi/dbi.h
+///
+/// Each stream is optional; streams which were not written are `None`.
+#[derive(Debug,Copy,Clone,Default,PartialEq,Eq)]
+pub struct DBIExtraStreams {
+    /// The stream of `FPO_DATA` records. See `ExtraStreamKind::Fpo`.
+    pub fpo: Option<u16>,
+    /// The stream of exception handling data. See `ExtraStreamKind::Exception`.
+    pub exception: Option<u16>,
+    /// The stream of fixup records. See `ExtraStreamKind::Fixup`.
+    pub fixup: Option<u16>,
+    /// The OMAP table from the transformed image to the original image. See
+    /// `ExtraStreamKind::OmapToSource`.
+    pub omap_to_source: Option<u16>,
+    /// The OMAP table from the original image to the transformed image. See
+    /// `ExtraStreamKind::OmapFromSource`.
+    pub omap_from_source: Option<u16>,
+    /// The stream of section headers. See `ExtraStreamKind::SectionHeaders`.
+    pub section_headers: Option<u16>,
+    /// The CLR token to record identifier map. See `ExtraStreamKind::TokenRidMap`.
+    pub token_rid_map: Option<u16>,
+    /// The copy of the `.xdata` section. See `ExtraStreamKind::Xdata`.
+    pub xdata: Option<u16>,
+    /// The copy of the `.pdata` section. See `ExtraStreamKind::Pdata`.
+    pub pdata: Option<u16>,
+    /// The stream of `FRAMEDATA` records. See `ExtraStreamKind::FrameData`.
+    pub framedata: Option<u16>,
+    /// The section headers of the original image. See `ExtraStreamKind::OriginalSectionHeaders`.
+    pub original_section_headers: Option<u16>,
+}
+
+impl DBIExtraStreams {
+    fn parse(mut buf: ParseBuffer) -> Result<DBIExtraStreams> {
+        // older linkers write fewer entries, so stop quietly when we run out
+        let mut next = || -> Result<Option<u16>> {
+            if buf.len() < 2 {
+                return Ok(None);
+            }
+            match buf.parse_u16()? {
+                0xffff => Ok(None),
+                stream => Ok(Some(stream)),
+            }
+        };
+
+        Ok(DBIExtraStreams {
+            fpo: next()?,
+            exception: next()?,
+            fixup: next()?,
+            omap_to_source: next()?,
+            omap_from_source: next()?,
+            section_headers: next()?,
+            token_rid_map: next()?,
+            xdata: next()?,
+            pdata: next()?,
+            framedata: next()?,
+            original_section_headers: next()?,
+        })
+    }
+
+    /// Returns the stream number of the given kind of stream, if it is present.
+    pub fn stream(&self, kind: ExtraStreamKind) -> Option<u16> {
+        match kind {
+            ExtraStreamKind::Fpo => self.fpo,
+            ExtraStreamKind::Exception => self.exception,
+            ExtraStreamKind::Fixup => self.fixup,
+            ExtraStreamKind::OmapToSource => self.omap_to_source,
+            ExtraStreamKind::OmapFromSource => self.omap_from_source,
+            ExtraStreamKind::SectionHeaders => self.section_headers,
+            ExtraStreamKind::TokenRidMap => self.token_rid_map,
+            ExtraStreamKind::Xdata => self.xdata,
+            ExtraStreamKind::Pdata => self.pdata,
+            ExtraStreamKind::FrameData => self.framedata,
+            ExtraStreamKind::OriginalSectionHeaders => self.original_section_headers,
+        }
+    }
+}
+
 /// Information about a module's contribution to a section.
 /// `struct SC` in Microsoft's code:
 /// https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/PDB/include/dbicommon.h#L42
 #[derive(Debug, Copy, Clone)]
 pub struct DBISectionContribution {
     /// The index of the section.
-    section: u16,
+    pub section: u16,
     _padding1: u16,
     /// The offset within the section.
-    offset: u32,
+    pub offset: u32,
     /// The size of the contribution, in bytes.
-    size: u32,
+    pub size: u32,
     /// The characteristics, which map to the `Characteristics` field of
     /// the [`IMAGE_SECTION_HEADER`][1] field in binaries.
     /// [1]: https://msdn.microsoft.com/en-us/library/windows/desktop/ms680341(v=vs.85).aspx
-    characteristics: u32,
+    pub characteristics: u32,
     /// The index of the module.
-    module: u16,
+    pub module: u16,
     _padding2: u16,
     /// CRC of the contribution(?)
-    data_crc: u32,
+    pub data_crc: u32,
     /// CRC of relocations(?)
-    reloc_crc: u32,
+    pub reloc_crc: u32,
+    /// The index of the section within the module's COFF object file, if known.
+    ///
+    /// This is only recorded by the `SC2` format, which is used by recent linkers.
+    pub coff_section: Option<u32>,
+}
+
+impl DBISectionContribution {
+    /// Returns `true` if this contribution covers the given `section:offset` address.
+    pub fn contains(&self, section: u16, offset: u32) -> bool {
+        self.section == section && offset >= self.offset &&
+            (offset - self.offset) < self.size
+    }
 }
 
 /// Information about a module parsed from the DBI stream. Named `MODI` in
@@ -286,6 +548,7 @@ fn parse_section_contribution(buf: &mut ParseBuffer) -> Result<DBISectionContrib
         _padding2: buf.parse_u16()?,
         data_crc: buf.parse_u32()?,
         reloc_crc: buf.parse_u32()?,
+        coff_section: None,
     })
 }
 
@@ -322,6 +585,12 @@ impl<'m> Module<'m> {
     pub fn object_file_name(&self) -> Cow<'m, str> {
         self.object_file_name.to_string()
     }
+    /// Whether this module has a module info stream for `PDB::module_info()` to read.
+    ///
+    /// Linker-generated and import modules often have none, and therefore no symbols or lines.
+    pub fn has_module_info(&self) -> bool {
+        self.info.stream != 0xffff
+    }
 }
 
 /// A `ModuleIter` iterates over the modules in the DBI section, producing `Module`s.
@@ -351,3 +620,548 @@ impl<'m> FallibleIterator for ModuleIter<'m> {
         }))
     }
 }
+
+/// The version of the section contributions substream containing `SC` records.
+const SECTION_CONTRIBUTION_VERSION_60: u32 = 0xeffe0000 + 19970605;
+
+/// The version of the section contributions substream containing `SC2` records, which extend
+/// `SC` with the index of the section within the COFF object file.
+const SECTION_CONTRIBUTION_VERSION_2: u32 = 0xeffe0000 + 20140516;
+
+/// A `SectionContributionIter` iterates over the section contributions in the DBI section,
+/// producing `DBISectionContribution`s.
+#[derive(Debug)]
+pub struct SectionContributionIter<'c> {
+    buf: ParseBuffer<'c>,
+    has_coff_section: bool,
+}
+
+impl<'c> SectionContributionIter<'c> {
+    fn parse(mut buf: ParseBuffer<'c>) -> Result<SectionContributionIter<'c>> {
+        let mut has_coff_section = false;
+
+        if buf.len() > 0 {
+            // the substream starts with a version number
+            match buf.parse_u32()? {
+                SECTION_CONTRIBUTION_VERSION_60 => {}
+                SECTION_CONTRIBUTION_VERSION_2 => { has_coff_section = true; }
+                _ => {
+                    return Err(Error::UnimplementedFeature("unsupported section contributions version"));
+                }
+            }
+        }
+
+        Ok(SectionContributionIter { buf, has_coff_section })
+    }
+}
+
+impl<'c> FallibleIterator for SectionContributionIter<'c> {
+    type Item = DBISectionContribution;
+    type Error = Error;
+
+    fn next(&mut self) -> result::Result<Option<Self::Item>, Self::Error> {
+        // see if we're at EOF
+        if self.buf.len() == 0 {
+            return Ok(None);
+        }
+
+        let mut contribution = parse_section_contribution(&mut self.buf)?;
+        if self.has_coff_section {
+            contribution.coff_section = Some(self.buf.parse_u32()?);
+        }
+
+        Ok(Some(contribution))
+    }
+}
+
+/// A `ContributionIndex` finds the `Module` and `DBISectionContribution` covering a
+/// `segment:offset` address using a binary search.
+///
+/// Build one by calling [`DebugInformation::contribution_index`][1].
+///
+/// [1]: struct.DebugInformation.html#method.contribution_index
+#[derive(Debug)]
+pub struct ContributionIndex<'m> {
+    modules: Vec<Module<'m>>,
+    contributions: Vec<DBISectionContribution>,
+    /// The furthest end of any contribution up to and including each index, within its section.
+    ends: Vec<u32>,
+}
+
+fn contribution_ends(contributions: &[DBISectionContribution]) -> Vec<u32> {
+    let mut ends: Vec<u32> = Vec::with_capacity(contributions.len());
+    for (index, contribution) in contributions.iter().enumerate() {
+        let end = contribution.offset.saturating_add(contribution.size);
+        let end = match ends.last() {
+            Some(&previous) if contributions[index - 1].section == contribution.section => {
+                cmp::max(previous, end)
+            }
+            _ => end,
+        };
+        ends.push(end);
+    }
+    ends
+}
+
+/// Finds the index of the contribution covering `section:offset` in `contributions`, which must
+/// be sorted by address.
+fn find_contribution(contributions: &[DBISectionContribution], ends: &[u32], section: u16, offset: u32) -> Option<usize> {
+    // find the last contribution starting at or before this address
+    let last = match contributions.binary_search_by_key(&(section, offset), |c| (c.section, c.offset)) {
+        Ok(index) => {
+            // binary search may land anywhere within a run of equal starts
+            let mut index = index;
+            while index + 1 < contributions.len() &&
+                (contributions[index + 1].section, contributions[index + 1].offset) == (section, offset) {
+                index += 1;
+            }
+            index
+        }
+        Err(0) => return None,
+        Err(index) => index - 1,
+    };
+
+    // walk back over the contributions which may still extend to this address, preferring the
+    // one which starts last, and the first of several which start at the same address
+    let mut found: Option<usize> = None;
+    for index in (0..last + 1).rev() {
+        let contribution = &contributions[index];
+        if contribution.section != section || ends[index] <= offset {
+            break;
+        }
+
+        if contribution.contains(section, offset) {
+            match found {
+                Some(f) if contributions[f].offset != contribution.offset => break,
+                _ => found = Some(index),
+            }
+        }
+    }
+
+    found
+}
+
+impl<'m> ContributionIndex<'m> {
+    /// Returns the modules in the order they appear in the DBI stream.
+    ///
+    /// `DBISectionContribution::module` is an index into this slice.
+    pub fn modules(&self) -> &[Module<'m>] {
+        self.modules.as_slice()
+    }
+
+    /// Returns the section contributions sorted by address, excluding empty contributions.
+    pub fn contributions(&self) -> &[DBISectionContribution] {
+        self.contributions.as_slice()
+    }
+
+    /// Finds the contribution covering `section:offset` along with the `Module` it came from.
+    ///
+    /// The linker may fold identical contributions from several modules into one, in which case
+    /// the first of them is returned. If contributions overlap, the one starting closest to the
+    /// address is returned. Returns `None` if no module contributed this address.
+    pub fn find(&self, section: u16, offset: u32) -> Option<(&Module<'m>, &DBISectionContribution)> {
+        let contribution = &self.contributions[find_contribution(&self.contributions, &self.ends, section, offset)?];
+        self.modules.get(contribution.module as usize)
+            .map(|module| (module, contribution))
+    }
+
+    /// Finds the contribution covering `rva` along with the `Module` it came from.
+    ///
+    /// `sections` are the section headers returned by `PDB::sections()`, which turn the RVA into a
+    /// `section:offset` address for `find()`. If the executable was rewritten after linking,
+    /// contributions refer to the original layout instead: either pass `PDB::original_sections()`
+    /// and an original RVA, or convert the RVA with `AddressTranslator::rva_to_segment_offset()`
+    /// and call `find()`.
+    pub fn find_rva(&self, rva: u32, sections: &[ImageSectionHeader]) -> Option<(&Module<'m>, &DBISectionContribution)> {
+        // sections are numbered from 1
+        let index = sections.iter().position(|section| section.contains_rva(rva))?;
+        self.find((index + 1) as u16, rva - sections[index].virtual_address)
+    }
+}
+
+/*
+https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/langapi/include/cvexefmt.h:
+    union OMFSegDescFlags {
+        struct {
+            unsigned short  fRead   :1;
+            unsigned short  fWrite  :1;
+            unsigned short  fExecute:1;
+            unsigned short  f32Bit  :1;
+            unsigned short  res1    :4;
+            unsigned short  fSel    :1;
+            unsigned short  fAbs    :1;
+            unsigned short  res2    :2;
+            unsigned short  fGroup  :1;
+            unsigned short  res3    :3;
+        };
+        unsigned short  flags;
+    };
+*/
+/// The flags of a `SectionMapEntry`.
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub struct SectionMapFlags(u16);
+impl SectionMapFlags {
+    /// Indicates if the segment is readable.
+    pub fn read(&self) -> bool                  { self.0 & 0x0001 != 0 }
+
+    /// Indicates if the segment is writable.
+    pub fn write(&self) -> bool                 { self.0 & 0x0002 != 0 }
+
+    /// Indicates if the segment is executable.
+    pub fn execute(&self) -> bool               { self.0 & 0x0004 != 0 }
+
+    /// Indicates if the descriptor describes a 32-bit linear address.
+    pub fn address_is_32bit(&self) -> bool      { self.0 & 0x0008 != 0 }
+
+    /// Indicates if `frame` is a selector rather than a section index.
+    pub fn is_selector(&self) -> bool           { self.0 & 0x0100 != 0 }
+
+    /// Indicates if `frame` is an absolute address.
+    pub fn is_absolute_address(&self) -> bool   { self.0 & 0x0200 != 0 }
+
+    /// Indicates if the descriptor represents a group.
+    pub fn is_group(&self) -> bool              { self.0 & 0x0400 != 0 }
+}
+
+/// A logical segment descriptor from the section map. Named `OMFSegMapDesc` in the Microsoft PDB
+/// source:
+/// https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/langapi/include/cvexefmt.h
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub struct SectionMapEntry {
+    /// The access and addressing flags of this segment.
+    pub flags: SectionMapFlags,
+    /// The logical overlay number.
+    pub overlay: u16,
+    /// The group index into the descriptor array.
+    pub group: u16,
+    /// The physical section this segment lives in; for PE images this is the 1-based index of
+    /// the section header.
+    pub frame: u16,
+    /// The byte index of the segment or group name in the sstSegName table, or `0xffff`.
+    pub section_name: u16,
+    /// The byte index of the class name in the sstSegName table, or `0xffff`.
+    pub class_name: u16,
+    /// The byte offset of this logical segment within the physical section.
+    pub offset: u32,
+    /// The size of the segment in bytes.
+    pub section_length: u32,
+}
+
+impl SectionMapEntry {
+    /// Translates an offset within this logical segment into an offset within the physical
+    /// section `frame`.
+    ///
+    /// Returns `None` if the offset lies outside the segment, or if the translated offset does not
+    /// fit into 32 bits.
+    pub fn frame_offset(&self, offset: u32) -> Option<u32> {
+        if offset < self.section_length {
+            self.offset.checked_add(offset)
+        } else {
+            None
+        }
+    }
+
+    /// Translates an offset within this logical segment into a linear address, which for PE
+    /// images is an RVA.
+    ///
+    /// `sections` are the section headers returned by `PDB::sections()`, which `frame` indexes.
+    /// Returns `None` if the offset lies outside the segment, if `frame` is an absolute address,
+    /// if it does not refer to one of `sections`, or if the address does not fit into 32 bits.
+    pub fn linear_address(&self, offset: u32, sections: &[ImageSectionHeader]) -> Option<u32> {
+        if self.flags.is_absolute_address() || self.frame == 0 {
+            return None;
+        }
+
+        let section = sections.get(self.frame as usize - 1)?;
+        section.virtual_address.checked_add(self.frame_offset(offset)?)
+    }
+}
+
+/// A `SectionMapIter` iterates over the section map in the DBI section, producing
+/// `SectionMapEntry`s.
+#[derive(Debug)]
+pub struct SectionMapIter<'s> {
+    buf: ParseBuffer<'s>,
+    /// The number of segment descriptors.
+    count: u16,
+    /// The number of logical segment descriptors.
+    logical_count: u16,
+}
+
+impl<'s> SectionMapIter<'s> {
+    fn parse(mut buf: ParseBuffer<'s>) -> Result<SectionMapIter<'s>> {
+        let (count, logical_count) = if buf.len() > 0 {
+            (buf.parse_u16()?, buf.parse_u16()?)
+        } else {
+            (0, 0)
+        };
+
+        Ok(SectionMapIter { buf, count, logical_count })
+    }
+
+    /// Returns the number of segment descriptors in the section map.
+    pub fn segment_count(&self) -> u16 {
+        self.count
+    }
+
+    /// Returns the number of logical segment descriptors in the section map.
+    pub fn logical_segment_count(&self) -> u16 {
+        self.logical_count
+    }
+}
+
+impl<'s> FallibleIterator for SectionMapIter<'s> {
+    type Item = SectionMapEntry;
+    type Error = Error;
+
+    fn next(&mut self) -> result::Result<Option<Self::Item>, Self::Error> {
+        // see if we're at EOF
+        if self.buf.len() == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(SectionMapEntry {
+            flags: SectionMapFlags(self.buf.parse_u16()?),
+            overlay: self.buf.parse_u16()?,
+            group: self.buf.parse_u16()?,
+            frame: self.buf.parse_u16()?,
+            section_name: self.buf.parse_u16()?,
+            class_name: self.buf.parse_u16()?,
+            offset: self.buf.parse_u32()?,
+            section_length: self.buf.parse_u32()?,
+        }))
+    }
+}
+
+/// The file info substream of the DBI stream, which lists the source files contributing to each
+/// module.
+///
+/// The substream starts with a module count and a source file count, followed by an array of
+/// per-module indices into the file list, an array of per-module file counts, the file list itself
+/// as offsets into a names buffer, and finally the names buffer. The source file count and
+/// per-module indices are only 16 bits wide and overflow on large programs, so both are
+/// reconstructed from the file counts instead.
+#[derive(Debug)]
+pub struct FileInfo<'s> {
+    /// `(first file, number of files)` for each module.
+    modules: Vec<(usize, usize)>,
+    /// The file list, as `u32` offsets into `names`.
+    file_name_offsets: &'s [u8],
+    names: &'s [u8],
+}
+
+impl<'s> FileInfo<'s> {
+    fn parse(mut buf: ParseBuffer<'s>) -> Result<FileInfo<'s>> {
+        if buf.len() == 0 {
+            return Ok(FileInfo { modules: Vec::new(), file_name_offsets: &[], names: &[] });
+        }
+
+        let module_count = buf.parse_u16()? as usize;
+        let _file_count = buf.parse_u16()?;
+
+        // skip the module indices; they're derived from the file counts instead
+        buf.take(module_count * 2)?;
+
+        let mut modules = Vec::with_capacity(module_count);
+        let mut file_count: usize = 0;
+        for _ in 0..module_count {
+            let count = buf.parse_u16()? as usize;
+            modules.push((file_count, count));
+            file_count += count;
+        }
+
+        let file_name_offsets = buf.take(file_count * 4)?;
+        let names_len = buf.len();
+        let names = buf.take(names_len)?;
+
+        Ok(FileInfo { modules, file_name_offsets, names })
+    }
+
+    /// Returns the number of modules described by the file info substream.
+    pub fn module_count(&self) -> usize {
+        self.modules.len()
+    }
+
+    /// Returns an iterator over the source file names of the module at `index`, as counted by
+    /// `DebugInformation::modules()`.
+    ///
+    /// Returns `None` if there is no such module.
+    pub fn module_files(&self, index: usize) -> Option<ModuleFileIter<'s>> {
+        self.modules.get(index).map(|&(first, count)| {
+            ModuleFileIter {
+                offsets: self.file_name_offsets[first * 4 .. (first + count) * 4].into(),
+                names: self.names,
+            }
+        })
+    }
+
+    /// Returns an iterator which produces a `ModuleFileIter` for each module, in the same order as
+    /// `DebugInformation::modules()`.
+    pub fn iter<'a>(&'a self) -> FileInfoIter<'a, 's> {
+        FileInfoIter { file_info: self, index: 0 }
+    }
+}
+
+/// A `FileInfoIter` iterates over the modules in a `FileInfo`, producing `ModuleFileIter`s.
+#[derive(Debug)]
+pub struct FileInfoIter<'a, 's: 'a> {
+    file_info: &'a FileInfo<'s>,
+    index: usize,
+}
+
+impl<'a, 's> FallibleIterator for FileInfoIter<'a, 's> {
+    type Item = ModuleFileIter<'s>;
+    type Error = Error;
+
+    fn next(&mut self) -> result::Result<Option<Self::Item>, Self::Error> {
+        let files = self.file_info.module_files(self.index);
+        if files.is_some() {
+            self.index += 1;
+        }
+        Ok(files)
+    }
+}
+
+/// A `ModuleFileIter` iterates over the source files contributing to a single module, producing
+/// their names.
+#[derive(Debug)]
+pub struct ModuleFileIter<'s> {
+    offsets: ParseBuffer<'s>,
+    names: &'s [u8],
+}
+
+impl<'s> FallibleIterator for ModuleFileIter<'s> {
+    type Item = RawString<'s>;
+    type Error = Error;
+
+    fn next(&mut self) -> result::Result<Option<Self::Item>, Self::Error> {
+        // see if we're at EOF
+        if self.offsets.len() == 0 {
+            return Ok(None);
+        }
+
+        let offset = self.offsets.parse_u32()? as usize;
+        if offset >= self.names.len() {
+            return Err(Error::UnexpectedEof);
+        }
+
+        let name = ParseBuffer::from(&self.names[offset..]).parse_cstring()?;
+        Ok(Some(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod section_map {
+        use dbi::*;
+
+        #[test]
+        fn test_frame_offset_overflow() {
+            let entry = SectionMapEntry {
+                flags: SectionMapFlags(0x010d),
+                overlay: 0,
+                group: 0,
+                frame: 1,
+                section_name: 0xffff,
+                class_name: 0xffff,
+                offset: 0xffff_ff00,
+                section_length: 0x1000,
+            };
+
+            assert_eq!(entry.frame_offset(0xff), Some(0xffff_ffff));
+            assert_eq!(entry.frame_offset(0x100), None);
+            assert_eq!(entry.linear_address(0x10, &[]), None);
+        }
+    }
+
+    mod modules {
+        use dbi::*;
+
+        fn module_record(stream: u16, name: &str) -> Vec<u8> {
+            let mut data = vec![0; 64];
+            data[34] = stream as u8;
+            data[35] = (stream >> 8) as u8;
+            data.extend_from_slice(name.as_bytes());
+            data.extend_from_slice(&[0, 0]);
+            let len = (data.len() + 3) & !3;
+            data.resize(len, 0);
+            data
+        }
+
+        #[test]
+        fn test_has_module_info() {
+            let mut data = module_record(12, "foo.obj");
+            data.extend(module_record(0xffff, "* Linker *"));
+
+            let mut modules = ModuleIter { buf: ParseBuffer::from(data.as_slice()) };
+
+            let module = modules.next().expect("parse").expect("module");
+            assert_eq!(module.module_name(), "foo.obj");
+            assert_eq!(module.info().stream, 12);
+            assert!(module.has_module_info());
+
+            let module = modules.next().expect("parse").expect("module");
+            assert_eq!(module.module_name(), "* Linker *");
+            assert!(!module.has_module_info());
+
+            assert!(modules.next().expect("parse").is_none());
+        }
+    }
+
+    mod contribution_index {
+        use dbi::*;
+
+        fn contribution(section: u16, offset: u32, size: u32, module: u16) -> DBISectionContribution {
+            DBISectionContribution {
+                section,
+                _padding1: 0,
+                offset,
+                size,
+                characteristics: 0,
+                module,
+                _padding2: 0,
+                data_crc: 0,
+                reloc_crc: 0,
+                coff_section: None,
+            }
+        }
+
+        #[test]
+        fn test_find_contribution() {
+            let contributions = [
+                contribution(1, 0x00, 0x10, 0),
+                contribution(1, 0x10, 0x40, 1),
+                contribution(1, 0x10, 0x40, 2),
+                contribution(1, 0x20, 0x08, 3),
+                contribution(1, 0x60, 0x10, 4),
+                contribution(2, 0x00, 0x10, 5),
+            ];
+            let ends = contribution_ends(&contributions);
+            assert_eq!(ends, vec![0x10, 0x50, 0x50, 0x50, 0x70, 0x10]);
+
+            let find = |section, offset| {
+                find_contribution(&contributions, &ends, section, offset).map(|i| contributions[i].module)
+            };
+
+            assert_eq!(find(1, 0x0f), Some(0));
+
+            // folded contributions resolve to the first of them
+            assert_eq!(find(1, 0x10), Some(1));
+            assert_eq!(find(1, 0x1f), Some(1));
+
+            // a nested contribution takes precedence over the one around it
+            assert_eq!(find(1, 0x20), Some(3));
+            assert_eq!(find(1, 0x27), Some(3));
+
+            // past the nested contribution, the longer one still covers the address
+            assert_eq!(find(1, 0x28), Some(1));
+            assert_eq!(find(1, 0x4f), Some(1));
+
+            assert_eq!(find(1, 0x50), None);
+            assert_eq!(find(1, 0x70), None);
+            assert_eq!(find(2, 0x00), Some(5));
+            assert_eq!(find(0, 0x00), None);
+            assert_eq!(find(3, 0x00), None);
+        }
+    }
+}
@@ -0,0 +1,524 @@
+// Copyright 2017 pdb Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Stack frame information for x86 code.
+//!
+//! 32-bit x86 code does not carry unwind tables in the executable. Instead, the PDB describes
+//! each function's stack frame, either with the classic `FPO_DATA` records or with the newer
+//! `FRAMEDATA` records, which include a program describing how to recover the caller's registers.
+
+use std::cmp;
+use std::result;
+
+use common::*;
+use msf::*;
+use strings::StringTable;
+use FallibleIterator;
+
+mod program;
+pub use self::program::{evaluate_program, Registers};
+
+/// The size of an `FPO_DATA` record.
+const FPO_DATA_SIZE: usize = 16;
+
+/// The size of a `FRAMEDATA` record.
+const FRAME_DATA_SIZE: usize = 32;
+
+/// The kind of stack frame described by an `FPOData` record.
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub enum FrameType {
+    /// A function which does not use `ebp` as a frame pointer. Named `FRAME_FPO`.
+    FPO,
+    /// A trap frame. Named `FRAME_TRAP`.
+    Trap,
+    /// A task state segment frame. Named `FRAME_TSS`.
+    TSS,
+    /// A standard frame which uses `ebp` as a frame pointer. Named `FRAME_NONFPO`.
+    NonFPO,
+}
+
+/// A classic frame pointer omission record, named `FPO_DATA` in `winnt.h`.
+///
+/// See the DIA SDK documentation:
+/// https://docs.microsoft.com/en-us/windows/desktop/api/winnt/ns-winnt-_fpo_data
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub struct FPOData {
+    /// The RVA of the first byte of the function.
+    pub rva: u32,
+    /// The size of the function's code, in bytes.
+    pub code_size: u32,
+    /// The size of the function's local variables, in bytes.
+    pub locals_size: u32,
+    /// The size of the function's parameters, in bytes.
+    pub params_size: u32,
+    /// The size of the function's prolog, in bytes.
+    pub prolog_size: u8,
+    /// The number of registers the function saves.
+    pub saved_regs_count: u8,
+    /// Whether the function uses structured exception handling.
+    pub has_seh: bool,
+    /// Whether the function uses `ebp` as a frame pointer.
+    pub uses_base_pointer: bool,
+    /// The kind of stack frame the function creates.
+    pub frame_type: FrameType,
+}
+
+impl FPOData {
+    fn parse(buf: &mut ParseBuffer) -> Result<FPOData> {
+        let rva = buf.parse_u32()?;
+        let code_size = buf.parse_u32()?;
+        let locals_dwords = buf.parse_u32()?;
+        let params_dwords = buf.parse_u16()?;
+        let attributes = buf.parse_u16()?;
+
+        Ok(FPOData {
+            rva,
+            code_size,
+            locals_size: locals_dwords.wrapping_mul(4),
+            params_size: u32::from(params_dwords) * 4,
+            prolog_size: (attributes & 0xff) as u8,
+            saved_regs_count: ((attributes >> 8) & 0x7) as u8,
+            has_seh: attributes & 0x0800 != 0,
+            uses_base_pointer: attributes & 0x1000 != 0,
+            frame_type: match attributes >> 14 {
+                0 => FrameType::FPO,
+                1 => FrameType::Trap,
+                2 => FrameType::TSS,
+                _ => FrameType::NonFPO,
+            },
+        })
+    }
+
+    /// Returns true if `rva` falls within the code described by this record.
+    pub fn contains(&self, rva: u32) -> bool {
+        rva >= self.rva && rva - self.rva < self.code_size
+    }
+}
+
+/// A "new FPO" frame data record, named `FRAMEDATA` in the Microsoft PDB source:
+/// https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h
+///
+/// Unlike `FPOData`, a `FrameData` record carries a program which computes the caller's
+/// registers. The program is stored in the PDB's string table; see `FrameTable::program_string()`.
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub struct FrameData {
+    /// The RVA of the first byte of the code described by this record.
+    pub rva: u32,
+    /// The size of the code described by this record, in bytes.
+    pub code_size: u32,
+    /// The size of the function's local variables, in bytes.
+    pub locals_size: u32,
+    /// The size of the function's parameters, in bytes.
+    pub params_size: u32,
+    /// The maximum number of bytes pushed onto the stack.
+    pub max_stack_size: u32,
+    /// The offset of the frame program in the PDB's string table, or `0` if there is none.
+    pub program: u32,
+    /// The size of the function's prolog, in bytes.
+    pub prolog_size: u16,
+    /// The size of the registers saved by the function, in bytes.
+    pub saved_regs_size: u16,
+    /// Whether the function uses structured exception handling.
+    pub has_seh: bool,
+    /// Whether the function uses C++ exception handling.
+    pub has_cpp_eh: bool,
+    /// Whether this record describes the start of a function, as opposed to a later block of
+    /// the same function.
+    pub is_function_start: bool,
+}
+
+impl FrameData {
+    fn parse(buf: &mut ParseBuffer) -> Result<FrameData> {
+        let rva = buf.parse_u32()?;
+        let code_size = buf.parse_u32()?;
+        let locals_size = buf.parse_u32()?;
+        let params_size = buf.parse_u32()?;
+        let max_stack_size = buf.parse_u32()?;
+        let program = buf.parse_u32()?;
+        let prolog_size = buf.parse_u16()?;
+        let saved_regs_size = buf.parse_u16()?;
+        let flags = buf.parse_u32()?;
+
+        Ok(FrameData {
+            rva,
+            code_size,
+            locals_size,
+            params_size,
+            max_stack_size,
+            program,
+            prolog_size,
+            saved_regs_size,
+            has_seh: flags & 0x1 != 0,
+            has_cpp_eh: flags & 0x2 != 0,
+            is_function_start: flags & 0x4 != 0,
+        })
+    }
+
+    /// Returns true if `rva` falls within the code described by this record.
+    pub fn contains(&self, rva: u32) -> bool {
+        rva >= self.rva && rva - self.rva < self.code_size
+    }
+}
+
+/// The table of `FPOData` records, stored in the stream `ExtraStreamKind::Fpo`.
+///
+/// Retrieved by `PDB::fpo_table()`.
+#[derive(Debug)]
+pub struct FPOTable<'s> {
+    stream: Stream<'s>,
+}
+
+pub fn new_fpo_table(stream: Stream) -> FPOTable {
+    FPOTable { stream }
+}
+
+impl<'s> FPOTable<'s> {
+    /// Returns an iterator over the records of this table, in ascending order of RVA.
+    pub fn iter(&self) -> FPOIter<'_> {
+        FPOIter { buf: self.stream.parse_buffer() }
+    }
+
+    /// Returns the number of records in this table.
+    pub fn len(&self) -> usize {
+        self.stream.parse_buffer().len() / FPO_DATA_SIZE
+    }
+
+    /// Returns true if this table contains no records.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Finds the record describing the function containing `rva`.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::UnexpectedEof` if the stream is truncated
+    pub fn find(&self, rva: u32) -> Result<Option<FPOData>> {
+        find_fpo(self.records()?, rva)
+    }
+
+    fn records(&self) -> Result<&[u8]> {
+        let mut buf = self.stream.parse_buffer();
+        let len = buf.len() - buf.len() % FPO_DATA_SIZE;
+        buf.take(len)
+    }
+}
+
+/// An iterator over `FPOData` records.
+#[derive(Debug)]
+pub struct FPOIter<'t> {
+    buf: ParseBuffer<'t>,
+}
+
+impl<'t> FallibleIterator for FPOIter<'t> {
+    type Item = FPOData;
+    type Error = Error;
+
+    fn next(&mut self) -> result::Result<Option<Self::Item>, Self::Error> {
+        if self.buf.len() == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(FPOData::parse(&mut self.buf)?))
+    }
+}
+
+/// The table of `FrameData` records, stored in the stream `ExtraStreamKind::FrameData`.
+///
+/// Retrieved by `PDB::frame_table()`.
+#[derive(Debug)]
+pub struct FrameTable<'s> {
+    stream: Stream<'s>,
+    strings: StringTable<'s>,
+}
+
+pub fn new_frame_table<'s>(stream: Stream<'s>, strings: StringTable<'s>) -> FrameTable<'s> {
+    FrameTable { stream, strings }
+}
+
+impl<'s> FrameTable<'s> {
+    /// Returns an iterator over the records of this table, in ascending order of RVA.
+    pub fn iter(&self) -> FrameDataIter<'_> {
+        let mut buf = self.stream.parse_buffer();
+        skip_frame_data_header(&mut buf);
+        FrameDataIter { buf }
+    }
+
+    /// Returns the number of records in this table.
+    pub fn len(&self) -> usize {
+        let mut buf = self.stream.parse_buffer();
+        skip_frame_data_header(&mut buf);
+        buf.len() / FRAME_DATA_SIZE
+    }
+
+    /// Returns true if this table contains no records.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Finds the innermost record describing the code at `rva`.
+    ///
+    /// A function may be described by several records, e.g. one for the function as a whole and
+    /// more for blocks within it. `find()` prefers the record which starts closest to `rva`.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::UnexpectedEof` if the stream is truncated
+    pub fn find(&self, rva: u32) -> Result<Option<FrameData>> {
+        find_frame_data(self.records()?, rva)
+    }
+
+    /// Looks up the frame program of `frame`, such as `$T0 $ebp = $eip $T0 4 + ^ =`.
+    ///
+    /// Returns `Ok(None)` if the record has no program.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::UnexpectedEof` if the program lies outside of the string table
+    pub fn program_string(&self, frame: &FrameData) -> Result<Option<RawString<'_>>> {
+        if frame.program == 0 {
+            return Ok(None);
+        }
+
+        self.strings.get(frame.program).map(Some)
+    }
+
+    /// Evaluates the frame program of `frame` to recover the caller's registers.
+    ///
+    /// This behaves like `evaluate_program()`, except that `.cbLocals`, `.cbParams`, and
+    /// `.cbSavedRegs` are filled in from `frame` if `registers` does not provide them. Programs
+    /// which search the stack for the return address use `.raSearch`, which the caller must
+    /// provide.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::InvalidFrameProgram` if the record has no program or it cannot be evaluated
+    /// * `Error::MemoryReadFailed` if `memory` could not read a dereferenced address
+    /// * `Error::UnexpectedEof` if the program lies outside of the string table
+    pub fn evaluate<F>(&self, frame: &FrameData, registers: &Registers, memory: F) -> Result<Registers>
+        where F: FnMut(u32) -> Option<u32>
+    {
+        let program = match self.program_string(frame)? {
+            Some(program) => program.to_string(),
+            None => return Err(Error::InvalidFrameProgram("frame data has no program")),
+        };
+
+        let mut registers = registers.clone();
+        registers.entry(".cbLocals".to_string()).or_insert(frame.locals_size);
+        registers.entry(".cbParams".to_string()).or_insert(frame.params_size);
+        registers.entry(".cbSavedRegs".to_string()).or_insert(u32::from(frame.saved_regs_size));
+
+        evaluate_program(&program, &registers, memory)
+    }
+
+    fn records(&self) -> Result<&[u8]> {
+        let mut buf = self.stream.parse_buffer();
+        skip_frame_data_header(&mut buf);
+        let len = buf.len() - buf.len() % FRAME_DATA_SIZE;
+        buf.take(len)
+    }
+}
+
+/// Skips the relocation pointer at the start of the `FRAMEDATA` stream.
+///
+/// The stream consists of a `u32` relocation pointer followed by the records. A stream too short
+/// to hold the pointer has no records.
+fn skip_frame_data_header(buf: &mut ParseBuffer) {
+    let len = cmp::min(buf.len(), 4);
+    buf.take(len).ok();
+}
+
+/// An iterator over `FrameData` records.
+#[derive(Debug)]
+pub struct FrameDataIter<'t> {
+    buf: ParseBuffer<'t>,
+}
+
+impl<'t> FallibleIterator for FrameDataIter<'t> {
+    type Item = FrameData;
+    type Error = Error;
+
+    fn next(&mut self) -> result::Result<Option<Self::Item>, Self::Error> {
+        if self.buf.len() == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(FrameData::parse(&mut self.buf)?))
+    }
+}
+
+/// Finds the `FPOData` record containing `rva` within a slice of records.
+fn find_fpo(records: &[u8], rva: u32) -> Result<Option<FPOData>> {
+    // FPO records never overlap, so only the last preceding record can contain `rva`
+    let count = find_preceding(records, FPO_DATA_SIZE, rva)?;
+    if count == 0 {
+        return Ok(None);
+    }
+
+    let fpo = record_at(records, FPO_DATA_SIZE, count - 1, FPOData::parse)?;
+    if fpo.contains(rva) {
+        Ok(Some(fpo))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Finds the innermost `FrameData` record containing `rva` within a slice of records.
+fn find_frame_data(records: &[u8], rva: u32) -> Result<Option<FrameData>> {
+    let count = find_preceding(records, FRAME_DATA_SIZE, rva)?;
+
+    // walk backwards through the blocks of this function
+    for index in (0..count).rev() {
+        let frame = record_at(records, FRAME_DATA_SIZE, index, FrameData::parse)?;
+        if frame.contains(rva) {
+            return Ok(Some(frame));
+        }
+        if frame.is_function_start {
+            break;
+        }
+    }
+
+    Ok(None)
+}
+
+/// Parses the `index`th fixed-size record of `records`.
+fn record_at<T, F>(records: &[u8], size: usize, index: usize, parse: F) -> Result<T>
+    where F: FnOnce(&mut ParseBuffer) -> Result<T>
+{
+    parse(&mut ParseBuffer::from(&records[index * size..(index + 1) * size]))
+}
+
+/// Returns the number of fixed-size records which start at or before `rva`. Every record type
+/// in this module starts with its RVA, and records are sorted by it.
+fn find_preceding(records: &[u8], size: usize, rva: u32) -> Result<usize> {
+    let (mut low, mut high) = (0, records.len() / size);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        let start = ParseBuffer::from(&records[mid * size..]).parse_u32()?;
+        if start <= rva {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    Ok(low)
+}
+
+#[cfg(test)]
+mod tests {
+    mod fpo {
+        use frame::*;
+
+        fn fpo_record(rva: u32, size: u32, attributes: u16) -> Vec<u8> {
+            let mut data = Vec::new();
+            for value in &[rva, size, 2] {
+                data.extend_from_slice(&[*value as u8, (*value >> 8) as u8,
+                                         (*value >> 16) as u8, (*value >> 24) as u8]);
+            }
+            data.extend_from_slice(&[3, 0, attributes as u8, (attributes >> 8) as u8]);
+            data
+        }
+
+        #[test]
+        fn test_parse() {
+            let data = fpo_record(0x1000, 0x20, 0x1000 | 0x0800 | 0x0300 | 0x05 | 0xc000);
+            let fpo = FPOData::parse(&mut ParseBuffer::from(data.as_slice())).expect("parse");
+            assert_eq!(fpo, FPOData {
+                rva: 0x1000,
+                code_size: 0x20,
+                locals_size: 8,
+                params_size: 12,
+                prolog_size: 5,
+                saved_regs_count: 3,
+                has_seh: true,
+                uses_base_pointer: true,
+                frame_type: FrameType::NonFPO,
+            });
+        }
+
+        #[test]
+        fn test_find() {
+            let mut data = fpo_record(0x1000, 0x20, 0);
+            data.extend(fpo_record(0x1040, 0x10, 0));
+            data.extend(fpo_record(0x1050, 0x30, 0));
+
+            let find = |rva| find_fpo(&data, rva).expect("find").map(|fpo| fpo.rva);
+
+            assert_eq!(find(0xfff), None);
+            assert_eq!(find(0x1000), Some(0x1000));
+            assert_eq!(find(0x101f), Some(0x1000));
+            assert_eq!(find(0x1020), None);
+            assert_eq!(find(0x104f), Some(0x1040));
+            assert_eq!(find(0x1050), Some(0x1050));
+            assert_eq!(find(0x1080), None);
+        }
+    }
+
+    mod frame_data {
+        use frame::*;
+
+        fn frame_record(rva: u32, size: u32, program: u32, flags: u32) -> Vec<u8> {
+            let mut data = Vec::new();
+            for value in &[rva, size, 0x10, 8, 0x40, program, 0x0004_0006, flags] {
+                data.extend_from_slice(&[*value as u8, (*value >> 8) as u8,
+                                         (*value >> 16) as u8, (*value >> 24) as u8]);
+            }
+            data
+        }
+
+        #[test]
+        fn test_parse() {
+            let data = frame_record(0x1000, 0x80, 0x1234, 0x6);
+            let frame = FrameData::parse(&mut ParseBuffer::from(data.as_slice())).expect("parse");
+            assert_eq!(frame, FrameData {
+                rva: 0x1000,
+                code_size: 0x80,
+                locals_size: 0x10,
+                params_size: 8,
+                max_stack_size: 0x40,
+                program: 0x1234,
+                prolog_size: 6,
+                saved_regs_size: 4,
+                has_seh: false,
+                has_cpp_eh: true,
+                is_function_start: true,
+            });
+        }
+
+        #[test]
+        fn test_header() {
+            let mut data = vec![0xaa, 0xbb, 0xcc, 0xdd];
+            data.extend(frame_record(0x1000, 0x80, 0, 4));
+            let mut buf = ParseBuffer::from(data.as_slice());
+            skip_frame_data_header(&mut buf);
+            assert_eq!(buf.len(), FRAME_DATA_SIZE);
+            assert_eq!(buf.parse_u32().expect("rva"), 0x1000);
+
+            let mut buf = ParseBuffer::from(&[0xaa, 0xbb][..]);
+            skip_frame_data_header(&mut buf);
+            assert_eq!(buf.len(), 0);
+        }
+
+        #[test]
+        fn test_find_nested() {
+            // a function at 0x1000..0x1100, with a block at 0x1010..0x1020, followed by another
+            // function at 0x1100..0x1200
+            let mut data = frame_record(0x1000, 0x100, 0, 4);
+            data.extend(frame_record(0x1010, 0x10, 0, 0));
+            data.extend(frame_record(0x1100, 0x100, 0, 4));
+
+            let find = |rva| find_frame_data(&data, rva).expect("find").map(|frame| frame.rva);
+
+            assert_eq!(find(0x0fff), None);
+            assert_eq!(find(0x1008), Some(0x1000));
+            assert_eq!(find(0x1018), Some(0x1010));
+            assert_eq!(find(0x1020), Some(0x1000));
+            assert_eq!(find(0x1100), Some(0x1100));
+            assert_eq!(find(0x1200), None);
+        }
+    }
+}
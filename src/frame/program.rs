@@ -0,0 +1,268 @@
+// Copyright 2017 pdb Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::collections::HashMap;
+
+use common::*;
+
+/// A set of named 32-bit values, keyed by the names used in frame programs.
+///
+/// Registers are named with a leading `$`, like `$eip` or `$esp`. Temporaries assigned by the
+/// program are named like `$T0`. Values the program takes from outside the frame, such as the
+/// stack location of the return address, are named with a leading `.`, like `.raSearch`.
+pub type Registers = HashMap<String, u32>;
+
+/// An item on the evaluation stack: either a number, or a name which has not been looked up yet.
+#[derive(Debug,Copy,Clone)]
+enum Operand<'p> {
+    Value(u32),
+    Name(&'p str),
+}
+
+/// Evaluates a `FRAMEDATA` program, such as `$T0 $ebp = $eip $T0 4 + ^ = $esp $T0 8 + =`.
+///
+/// Frame programs are postfix expressions over 32-bit unsigned values. Operands are decimal (or
+/// `0x`-prefixed hexadecimal) numbers and names; the operators are:
+///
+/// * `+`, `-`, `*`, `/`, `%`: wrapping arithmetic
+/// * `@`: `a b @` aligns `a` down to a multiple of `b`
+/// * `^`: `a ^` reads the 32-bit value at address `a`
+/// * `=`: `name a =` assigns `a` to `name`
+///
+/// `registers` holds the callee's registers, plus any `.`-prefixed values the program needs.
+/// `memory` reads the 32-bit value at an address of the stopped process, returning `None` if the
+/// address is not readable.
+///
+/// Returns the caller's registers: the registers in `registers`, updated with the values the
+/// program assigned. Registers the program leaves alone, like callee-saved registers the function
+/// never touched, keep their values. Temporaries like `$T0` and `.`-prefixed values are omitted.
+///
+/// # Errors
+///
+/// * `Error::InvalidFrameProgram` if the program is malformed, refers to a name which has not
+///   been assigned, or divides by zero
+/// * `Error::MemoryReadFailed` if `memory` could not read a dereferenced address
+///
+/// # Example
+///
+/// ```
+/// # fn test() -> pdb::Result<()> {
+/// let mut registers = pdb::Registers::new();
+/// registers.insert("$ebp".to_string(), 0x1000);
+/// registers.insert("$ebx".to_string(), 0x42);
+///
+/// let stack = |address| match address {
+///     0x1000 => Some(0x2000),
+///     0x1004 => Some(0x401234),
+///     _ => None,
+/// };
+///
+/// let caller = pdb::evaluate_program(
+///     "$T0 $ebp = $eip $T0 4 + ^ = $ebp $T0 ^ = $esp $T0 8 + =", &registers, stack)?;
+/// assert_eq!(caller["$eip"], 0x401234);
+/// assert_eq!(caller["$ebp"], 0x2000);
+/// assert_eq!(caller["$esp"], 0x1008);
+/// assert_eq!(caller["$ebx"], 0x42);
+/// assert!(!caller.contains_key("$T0"));
+/// # Ok(())
+/// # }
+/// # test().expect("test");
+/// ```
+pub fn evaluate_program<F>(program: &str, registers: &Registers, mut memory: F) -> Result<Registers>
+    where F: FnMut(u32) -> Option<u32>
+{
+    let mut stack: Vec<Operand> = Vec::new();
+    let mut assigned: HashMap<&str, u32> = HashMap::new();
+
+    {
+        let lookup = |operand: Operand, assigned: &HashMap<&str, u32>| -> Result<u32> {
+            match operand {
+                Operand::Value(value) => Ok(value),
+                Operand::Name(name) => assigned.get(name).or_else(|| registers.get(name))
+                    .cloned()
+                    .ok_or(Error::InvalidFrameProgram("reference to an unassigned name")),
+            }
+        };
+
+        for token in program.split_whitespace() {
+            match token {
+                "+" | "-" | "*" | "/" | "%" | "@" => {
+                    let b = lookup(pop(&mut stack)?, &assigned)?;
+                    let a = lookup(pop(&mut stack)?, &assigned)?;
+                    let value = match token {
+                        "+" => a.wrapping_add(b),
+                        "-" => a.wrapping_sub(b),
+                        "*" => a.wrapping_mul(b),
+                        "/" if b == 0 => return Err(Error::InvalidFrameProgram("division by zero")),
+                        "/" => a / b,
+                        "%" if b == 0 => return Err(Error::InvalidFrameProgram("division by zero")),
+                        "%" => a % b,
+                        "@" if b == 0 => return Err(Error::InvalidFrameProgram("alignment of zero")),
+                        _ => a & !(b - 1),
+                    };
+                    stack.push(Operand::Value(value));
+                }
+                "^" => {
+                    let address = lookup(pop(&mut stack)?, &assigned)?;
+                    let value = memory(address).ok_or(Error::MemoryReadFailed(address))?;
+                    stack.push(Operand::Value(value));
+                }
+                "=" => {
+                    let value = lookup(pop(&mut stack)?, &assigned)?;
+                    match pop(&mut stack)? {
+                        Operand::Name(name) => { assigned.insert(name, value); }
+                        Operand::Value(_) => {
+                            return Err(Error::InvalidFrameProgram("assignment to a number"));
+                        }
+                    }
+                }
+                _ => stack.push(parse_operand(token)?),
+            }
+        }
+    }
+
+    if !stack.is_empty() {
+        return Err(Error::InvalidFrameProgram("values left on the stack"));
+    }
+
+    let mut result: Registers = registers.iter()
+        .filter(|&(name, _)| name.starts_with('$') && !is_temporary(name))
+        .map(|(name, value)| (name.clone(), *value))
+        .collect();
+
+    for (name, value) in assigned {
+        if !is_temporary(name) {
+            result.insert(name.to_string(), value);
+        }
+    }
+
+    Ok(result)
+}
+
+fn pop<'p>(stack: &mut Vec<Operand<'p>>) -> Result<Operand<'p>> {
+    stack.pop().ok_or(Error::InvalidFrameProgram("stack underflow"))
+}
+
+fn parse_operand(token: &str) -> Result<Operand<'_>> {
+    if token.starts_with('$') || token.starts_with('.') {
+        return Ok(Operand::Name(token));
+    }
+
+    let value = if token.starts_with("0x") || token.starts_with("0X") {
+        u32::from_str_radix(&token[2..], 16)
+    } else {
+        token.parse::<u32>()
+    };
+
+    value.map(Operand::Value).map_err(|_| Error::InvalidFrameProgram("unrecognized token"))
+}
+
+/// Returns true if `name` is a temporary like `$T0`, rather than a register.
+fn is_temporary(name: &str) -> bool {
+    name.starts_with("$T") && name.len() > 2 && name[2..].bytes().all(|b| b.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    mod evaluate {
+        use frame::program::*;
+
+        fn registers(values: &[(&str, u32)]) -> Registers {
+            values.iter().map(|&(name, value)| (name.to_string(), value)).collect()
+        }
+
+        fn no_memory(_: u32) -> Option<u32> {
+            None
+        }
+
+        #[test]
+        fn test_arithmetic() {
+            let result = evaluate_program(
+                "$eax 1 2 + 3 * = $ebx 10 3 - = $ecx 17 5 % = $edx 17 5 / = $esi 0x1237 16 @ =",
+                &Registers::new(), no_memory).expect("evaluate");
+            assert_eq!(result, registers(&[
+                ("$eax", 9), ("$ebx", 7), ("$ecx", 2), ("$edx", 3), ("$esi", 0x1230),
+            ]));
+        }
+
+        #[test]
+        fn test_wrapping() {
+            let result = evaluate_program("$eax 1 2 - = $ebx 4294967295 1 + =",
+                                          &Registers::new(), no_memory).expect("evaluate");
+            assert_eq!(result["$eax"], 0xffff_ffff);
+            assert_eq!(result["$ebx"], 0);
+        }
+
+        #[test]
+        fn test_typical_program() {
+            // a typical program for a function which saved ebx and esi
+            let program = "$T0 .raSearch = $eip $T0 ^ = $esp $T0 4 + = \
+                           $ebx $T0 8 - ^ = $esi $T0 12 - ^ =";
+            let context = registers(&[
+                ("$esp", 0x8000), ("$eip", 0x401000), ("$ebp", 0x9000), (".raSearch", 0x8010),
+            ]);
+            let memory = |address| match address {
+                0x8010 => Some(0x402000),
+                0x8008 => Some(0xbbbb),
+                0x8004 => Some(0x5555),
+                _ => None,
+            };
+
+            // ebp is not restored by the program, so the caller's value is the callee's value
+            let result = evaluate_program(program, &context, memory).expect("evaluate");
+            assert_eq!(result, registers(&[
+                ("$eip", 0x402000), ("$esp", 0x8014), ("$ebp", 0x9000), ("$ebx", 0xbbbb),
+                ("$esi", 0x5555),
+            ]));
+        }
+
+        #[test]
+        fn test_assignment_shadows_input() {
+            // later tokens see the new value of a register
+            let result = evaluate_program("$ebp $ebp 4 + = $esp $ebp =",
+                                          &registers(&[("$ebp", 0x100)]), no_memory)
+                .expect("evaluate");
+            assert_eq!(result["$ebp"], 0x104);
+            assert_eq!(result["$esp"], 0x104);
+        }
+
+        #[test]
+        fn test_errors() {
+            let context = registers(&[("$esp", 0x100)]);
+            let check = |program: &str| evaluate_program(program, &context, no_memory);
+
+            match check("$eax +") {
+                Err(Error::InvalidFrameProgram(_)) => (),
+                _ => panic!("expected stack underflow"),
+            }
+            match check("$eax $ebx =") {
+                Err(Error::InvalidFrameProgram(_)) => (),
+                _ => panic!("expected unassigned name"),
+            }
+            match check("$eax 1 0 / =") {
+                Err(Error::InvalidFrameProgram(_)) => (),
+                _ => panic!("expected division by zero"),
+            }
+            match check("1 2 =") {
+                Err(Error::InvalidFrameProgram(_)) => (),
+                _ => panic!("expected assignment to a number"),
+            }
+            match check("$eax 1 = 2") {
+                Err(Error::InvalidFrameProgram(_)) => (),
+                _ => panic!("expected leftover values"),
+            }
+            match check("$eax foo =") {
+                Err(Error::InvalidFrameProgram(_)) => (),
+                _ => panic!("expected unrecognized token"),
+            }
+            match check("$eip $esp ^ =") {
+                Err(Error::MemoryReadFailed(0x100)) => (),
+                _ => panic!("expected memory read failure"),
+            }
+        }
+    }
+}
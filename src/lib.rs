@@ -51,21 +51,37 @@ extern crate uuid;
 // modules
 mod common;
 mod dbi;
+mod frame;
+mod line_index;
 mod module_info;
 mod msf;
+mod omap;
 mod pdb;
+mod pe;
 mod source;
+mod strings;
 mod symbol;
 mod tpi;
 mod pdbi;
 
 // exports
-pub use common::{Error,Result,TypeIndex,RawString,Variant};
-pub use dbi::{DebugInformation, Module, ModuleIter};
-pub use module_info::ModuleInfo;
+pub use common::{Error,Result,TypeIndex,ItemIndex,FileIndex,RawString,Variant};
+pub use dbi::{ContributionIndex, DBIExtraStreams, DBISectionContribution, DebugInformation,
+              ExtraStreamKind, FileInfo, FileInfoIter, Module, ModuleFileIter, ModuleIter,
+              SectionContributionIter, SectionMapEntry, SectionMapFlags, SectionMapIter};
+pub use frame::{evaluate_program, FPOData, FPOIter, FPOTable, FrameData, FrameDataIter, FrameTable,
+                FrameType, Registers};
+pub use line_index::{LineIndex, SourceLine};
+pub use module_info::{CompileInfo, CrossModuleExport, CrossModuleExportIter, CrossModuleImport,
+                      CrossModuleImportIter, CrossModuleRef, CrossModuleTarget, FileChecksum,
+                      FileChecksumIter, FileChecksumKind, InlineeLine, InlineeLineIter, LineInfo,
+                      LineInfoKind, LineIter, ModuleInfo};
 pub use pdbi::{NameIter, PDBInformation, StreamName, StreamNames};
+pub use omap::{AddressTranslator, OMAPRecord, OMAPTable};
 pub use pdb::PDB;
+pub use pe::ImageSectionHeader;
 pub use source::*;
+pub use strings::{StringTable, StringTableHashVersion};
 pub use symbol::*;
 pub use tpi::*;
 
@@ -0,0 +1,249 @@
+// Copyright 2017 pdb Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::collections::HashMap;
+
+use common::*;
+use module_info::{LineInfo, LineInfoKind};
+
+/// A range of code mapped to a location in a source file, as stored in a `LineIndex`.
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub struct SourceLine {
+    /// The RVA of the first byte of code.
+    pub rva: u32,
+    /// The number of bytes of code covered by this line.
+    pub length: u32,
+    /// The source file, which can be resolved with `LineIndex::file_name()`.
+    pub file: usize,
+    /// The first line of the source range.
+    pub line_start: u32,
+    /// The last line of the source range.
+    pub line_end: u32,
+    /// The first column of the source range, if columns were recorded.
+    pub column_start: Option<u16>,
+    /// The column just past the source range, if columns were recorded.
+    pub column_end: Option<u16>,
+    /// Whether this is the start of a statement or an expression.
+    pub kind: LineInfoKind,
+}
+
+impl SourceLine {
+    /// Returns true if the code of this line contains `rva`.
+    pub fn contains(&self, rva: u32) -> bool {
+        rva >= self.rva && rva - self.rva < self.length
+    }
+}
+
+/// An index of the line information of every module in a PDB, keyed by RVA and by source line.
+///
+/// Individual modules only describe their own code, in terms of `segment:offset` addresses and
+/// per-module file indices. A `LineIndex` collects the lines of all modules, translates their
+/// addresses to RVAs, and resolves their files to a shared list of names, so that addresses can
+/// be mapped to source lines and back in logarithmic time.
+///
+/// If the executable was rewritten after linking, only the start address of each line is
+/// translated through the OMAP tables; its length is kept as is. A line whose code was split
+/// across several OMAP blocks may therefore report a range which does not match the code at its
+/// translated RVA.
+///
+/// Build one with `PDB::line_index()`.
+#[derive(Debug,Clone,Default)]
+pub struct LineIndex {
+    /// Source file names, indexed by `SourceLine::file`.
+    files: Vec<Vec<u8>>,
+
+    /// Maps source file names back to their index in `files`.
+    file_ids: HashMap<Vec<u8>, usize>,
+
+    /// All lines, sorted by RVA.
+    lines: Vec<SourceLine>,
+
+    /// Indices into `lines`, sorted by file, first line, and RVA.
+    by_source: Vec<usize>,
+}
+
+impl LineIndex {
+    /// Returns all lines of the index, sorted by RVA.
+    pub fn lines(&self) -> &[SourceLine] {
+        &self.lines
+    }
+
+    /// Returns the number of distinct source files in the index.
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Returns the name of the source file `file`, or `None` if there is no such file.
+    pub fn file_name(&self, file: usize) -> Option<RawString<'_>> {
+        self.files.get(file).map(|name| RawString::from(name.as_slice()))
+    }
+
+    /// Finds the source file with the given name.
+    ///
+    /// Names are compared exactly as they are stored in the PDB, which is usually the full path
+    /// the compiler saw.
+    pub fn find_file(&self, name: RawString) -> Option<usize> {
+        self.file_ids.get(name.as_bytes()).cloned()
+    }
+
+    /// Finds the line containing the code at `rva`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn test() -> pdb::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb::PDB::open(file)?;
+    /// let index = pdb.line_index()?;
+    /// if let Some(line) = index.find(0x64f0) {
+    ///     println!("{}:{}", index.file_name(line.file).unwrap(), line.line_start);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # test().expect("test");
+    /// ```
+    pub fn find(&self, rva: u32) -> Option<&SourceLine> {
+        // the last line starting at or before the address is the only candidate
+        let index = self.lines.partition_point(|line| line.rva <= rva);
+        if index == 0 {
+            return None;
+        }
+
+        let line = &self.lines[index - 1];
+        if line.contains(rva) {
+            Some(line)
+        } else {
+            None
+        }
+    }
+
+    /// Finds the code generated for line `line` of the source file `file`, sorted by RVA.
+    ///
+    /// This returns the lines whose source range starts at `line`. Code for a single source line
+    /// is frequently split into several ranges, for example by inlining or by the optimizer.
+    pub fn find_source(&self, file: usize, line: u32) -> Vec<&SourceLine> {
+        let key = |index: &usize| {
+            let source = &self.lines[*index];
+            (source.file, source.line_start)
+        };
+
+        let start = self.by_source.partition_point(|index| key(index) < (file, line));
+        let end = self.by_source.partition_point(|index| key(index) <= (file, line));
+        self.by_source[start..end].iter().map(|index| &self.lines[*index]).collect()
+    }
+}
+
+/// Collects lines from several modules into a `LineIndex`.
+#[derive(Debug,Default)]
+pub(crate) struct LineIndexBuilder {
+    index: LineIndex,
+}
+
+impl LineIndexBuilder {
+    pub fn new() -> Self {
+        LineIndexBuilder::default()
+    }
+
+    /// Adds a line whose code starts at `rva`, and which belongs to the file named `file_name`.
+    pub fn add(&mut self, rva: u32, line: &LineInfo, file_name: RawString) {
+        // lines without code cannot be found by address, and only confuse the lookup
+        if line.length == 0 {
+            return;
+        }
+
+        let files = &mut self.index.files;
+        let file = *self.index.file_ids.entry(file_name.as_bytes().to_vec()).or_insert_with(|| {
+            files.push(file_name.as_bytes().to_vec());
+            files.len() - 1
+        });
+
+        self.index.lines.push(SourceLine {
+            rva,
+            length: line.length,
+            file,
+            line_start: line.line_start,
+            line_end: line.line_end,
+            column_start: line.column_start,
+            column_end: line.column_end,
+            kind: line.kind,
+        });
+    }
+
+    pub fn build(self) -> LineIndex {
+        let mut index = self.index;
+        index.lines.sort_by_key(|line| (line.rva, line.length));
+
+        let mut by_source: Vec<usize> = (0..index.lines.len()).collect();
+        {
+            let lines = &index.lines;
+            by_source.sort_by_key(|i| (lines[*i].file, lines[*i].line_start, lines[*i].rva));
+        }
+        index.by_source = by_source;
+
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod lookup {
+        use line_index::*;
+
+        fn line(offset: u32, length: u32, line_start: u32) -> LineInfo {
+            LineInfo {
+                offset,
+                segment: 1,
+                length,
+                file_index: 0,
+                line_start,
+                line_end: line_start,
+                column_start: None,
+                column_end: None,
+                kind: LineInfoKind::Statement,
+            }
+        }
+
+        fn index() -> LineIndex {
+            let mut builder = LineIndexBuilder::new();
+            builder.add(0x1010, &line(0x10, 0x8, 3), RawString::from("b.c"));
+            builder.add(0x1000, &line(0x0, 0x8, 1), RawString::from("a.c"));
+            builder.add(0x1008, &line(0x8, 0x8, 2), RawString::from("a.c"));
+            builder.add(0x1018, &line(0x18, 0x0, 4), RawString::from("a.c"));
+            builder.add(0x2000, &line(0x1000, 0x10, 1), RawString::from("a.c"));
+            builder.build()
+        }
+
+        #[test]
+        fn test_find() {
+            let index = index();
+            assert_eq!(index.file_count(), 2);
+            assert_eq!(index.lines().len(), 4);
+
+            assert_eq!(index.find(0xfff), None);
+            assert_eq!(index.find(0x1000).map(|line| line.line_start), Some(1));
+            assert_eq!(index.find(0x100f).map(|line| line.line_start), Some(2));
+            assert_eq!(index.find(0x1017).map(|line| line.line_start), Some(3));
+            assert_eq!(index.find(0x1018), None);
+            assert_eq!(index.find(0x200f).map(|line| line.line_start), Some(1));
+
+            let file = index.find(0x1010).expect("line").file;
+            assert_eq!(index.file_name(file), Some(RawString::from("b.c")));
+        }
+
+        #[test]
+        fn test_find_source() {
+            let index = index();
+            let a = index.find_file(RawString::from("a.c")).expect("a.c");
+            assert_eq!(index.find_file(RawString::from("c.c")), None);
+
+            let ranges: Vec<_> = index.find_source(a, 1).iter().map(|l| (l.rva, l.length)).collect();
+            assert_eq!(ranges, vec![(0x1000, 0x8), (0x2000, 0x10)]);
+            assert!(index.find_source(a, 3).is_empty());
+            assert!(index.find_source(a, 4).is_empty());
+        }
+    }
+}
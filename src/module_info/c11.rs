@@ -0,0 +1,227 @@
+// Copyright 2017 pdb Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! C11 line information, as written by Visual C++ 6.0 and earlier.
+//!
+//! The C11 area of a module stream is the CodeView `sstSrcModule` subsection. It consists of a
+//! module header, a file record for each source file, and a line table for each segment a file
+//! contributed code to. All offsets are relative to the start of the area. The formats are
+//! described by `OMFSourceModule`, `OMFSourceFile`, and `OMFSourceLine` in `cvexefmt.h`:
+//! https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/langapi/include/cvexefmt.h
+
+use std::result;
+use std::vec;
+
+use common::*;
+use FallibleIterator;
+use super::{LineInfo, LineInfoKind};
+
+/// Reads the `index`th entry of a `u32` array starting at `offset`.
+fn u32_at(data: &[u8], offset: usize, index: usize) -> Result<u32> {
+    let position = offset + index * 4;
+    if position > data.len() {
+        return Err(Error::UnexpectedEof);
+    }
+    ParseBuffer::from(&data[position..]).parse_u32()
+}
+
+/// Returns a `ParseBuffer` positioned at `offset`.
+fn buffer_at(data: &[u8], offset: usize) -> Result<ParseBuffer<'_>> {
+    if offset > data.len() {
+        return Err(Error::UnexpectedEof);
+    }
+    Ok(ParseBuffer::from(&data[offset..]))
+}
+
+/// Parses the lines of the file record at `file_index`, sorted by offset.
+fn parse_file_lines(data: &[u8], file_index: FileIndex) -> Result<Vec<LineInfo>> {
+    let file_offset = file_index as usize;
+    let mut buf = buffer_at(data, file_offset)?;
+    let segment_count = buf.parse_u16()? as usize;
+    let _reserved = buf.parse_u16()?;
+
+    // the line table offsets are followed by a (start, end) range for each segment
+    let ranges_offset = file_offset + 4 + segment_count * 4;
+
+    let mut lines = Vec::new();
+    for i in 0..segment_count {
+        let table_offset = u32_at(data, file_offset + 4, i)? as usize;
+        let range_end = u32_at(data, ranges_offset, i * 2 + 1)?;
+
+        let mut table = buffer_at(data, table_offset)?;
+        let segment = table.parse_u16()?;
+        let pair_count = table.parse_u16()? as usize;
+
+        // all the offsets come first, then all the line numbers
+        let mut line_numbers = buffer_at(data, table_offset + 4 + pair_count * 4)?;
+        let first = lines.len();
+        for _ in 0..pair_count {
+            let line = u32::from(line_numbers.parse_u16()?);
+            lines.push(LineInfo {
+                offset: table.parse_u32()?,
+                segment,
+                length: 0,
+                file_index,
+                line_start: line,
+                line_end: line,
+                column_start: None,
+                column_end: None,
+                kind: LineInfoKind::Statement,
+            });
+        }
+
+        // each line extends to the next one, and the last one to the end of the segment's range,
+        // which is the offset of its last byte
+        lines[first..].sort_by_key(|line| line.offset);
+        for j in first..lines.len() {
+            let end = match lines.get(j + 1) {
+                Some(next) => next.offset,
+                None => range_end.wrapping_add(1),
+            };
+            lines[j].length = end.saturating_sub(lines[j].offset);
+        }
+    }
+
+    Ok(lines)
+}
+
+/// Returns the name of the file record at `file_index`.
+pub fn file_name(data: &[u8], file_index: FileIndex) -> Result<RawString<'_>> {
+    let file_offset = file_index as usize;
+    let mut buf = buffer_at(data, file_offset)?;
+    let segment_count = buf.parse_u16()? as usize;
+
+    // skip the header, line table offsets, and ranges to reach the name
+    let mut name = buffer_at(data, file_offset + 4 + segment_count * 12)?;
+    name.parse_u8_pascal_string()
+}
+
+/// Iterates over the lines of every file in a C11 line information area.
+#[derive(Debug)]
+pub struct LineIterator<'a> {
+    data: &'a [u8],
+    file_count: usize,
+    next_file: usize,
+    lines: vec::IntoIter<LineInfo>,
+}
+
+impl<'a> LineIterator<'a> {
+    pub fn new(data: &'a [u8]) -> Result<Self> {
+        let file_count = ParseBuffer::from(data).parse_u16()? as usize;
+        Ok(LineIterator {
+            data,
+            file_count,
+            next_file: 0,
+            lines: Vec::new().into_iter(),
+        })
+    }
+}
+
+impl<'a> FallibleIterator for LineIterator<'a> {
+    type Item = LineInfo;
+    type Error = Error;
+
+    fn next(&mut self) -> result::Result<Option<Self::Item>, Self::Error> {
+        loop {
+            if let Some(line) = self.lines.next() {
+                return Ok(Some(line));
+            }
+
+            if self.next_file >= self.file_count {
+                return Ok(None);
+            }
+
+            // the file record offsets follow the u16 file and segment counts
+            let file_index = u32_at(self.data, 4, self.next_file)?;
+            self.next_file += 1;
+            self.lines = parse_file_lines(self.data, file_index)?.into_iter();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod lines {
+        use module_info::c11::*;
+
+        fn push_u16(data: &mut Vec<u8>, value: u16) {
+            data.extend_from_slice(&[value as u8, (value >> 8) as u8]);
+        }
+
+        fn push_u32(data: &mut Vec<u8>, value: u32) {
+            data.extend_from_slice(&[value as u8, (value >> 8) as u8,
+                                     (value >> 16) as u8, (value >> 24) as u8]);
+        }
+
+        /// A module with one file, "a.c", contributing to one segment.
+        fn module() -> Vec<u8> {
+            let mut data = Vec::new();
+
+            // OMFSourceModule at 0: one file, one segment
+            push_u16(&mut data, 1);
+            push_u16(&mut data, 1);
+            push_u32(&mut data, 20);          // baseSrcFile[0]
+            push_u32(&mut data, 0x10);        // start[0]
+            push_u32(&mut data, 0x3f);        // end[0]
+            push_u16(&mut data, 1);           // seg[0]
+            push_u16(&mut data, 0);           // padding
+            assert_eq!(data.len(), 20);
+
+            // OMFSourceFile at 20: one segment, named "a.c"
+            push_u16(&mut data, 1);
+            push_u16(&mut data, 0);
+            push_u32(&mut data, 40);          // baseSrcLn[0]
+            push_u32(&mut data, 0x10);        // start[0]
+            push_u32(&mut data, 0x3f);        // end[0]
+            data.extend_from_slice(&[3, b'a', b'.', b'c']);
+            assert_eq!(data.len(), 40);
+
+            // OMFSourceLine at 40: three lines in segment 1, out of order
+            push_u16(&mut data, 1);
+            push_u16(&mut data, 3);
+            push_u32(&mut data, 0x10);
+            push_u32(&mut data, 0x30);
+            push_u32(&mut data, 0x18);
+            push_u16(&mut data, 7);
+            push_u16(&mut data, 9);
+            push_u16(&mut data, 8);
+
+            data
+        }
+
+        #[test]
+        fn test_lines() {
+            let data = module();
+            let lines: Vec<LineInfo> = LineIterator::new(&data).expect("new").collect()
+                .expect("collect");
+
+            let summary: Vec<_> = lines.iter()
+                .map(|line| (line.segment, line.offset, line.length, line.line_start, line.file_index))
+                .collect();
+            assert_eq!(summary, vec![
+                (1, 0x10, 8, 7, 20),
+                (1, 0x18, 0x18, 8, 20),
+                (1, 0x30, 0x10, 9, 20),
+            ]);
+        }
+
+        #[test]
+        fn test_file_name() {
+            let data = module();
+            assert_eq!(file_name(&data, 20).expect("file name").as_bytes(), b"a.c");
+        }
+
+        #[test]
+        fn test_truncated() {
+            let data = module();
+            match LineIterator::new(&data[..30]).expect("new").collect::<Vec<_>>() {
+                Err(Error::UnexpectedEof) => (),
+                _ => panic!("expected UnexpectedEof"),
+            }
+        }
+    }
+}
@@ -0,0 +1,521 @@
+// Copyright 2017 pdb Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! C13 debug subsections, as written by Visual C++ 7.0 and later.
+//!
+//! The C13 area of a module stream is a series of subsections, each with a kind and a length.
+//! The formats are described by `DEBUG_S_SUBSECTION_TYPE` and friends in `cvinfo.h`:
+//! https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h
+
+use std::result;
+use std::vec;
+
+use common::*;
+use FallibleIterator;
+use super::{CrossModuleExport, CrossModuleImport, FileChecksum, InlineeLine, LineInfo, LineInfoKind};
+
+/// Set on subsection kinds which should be ignored by readers.
+const DEBUG_S_IGNORE: u32 = 0x8000_0000;
+
+pub const DEBUG_S_LINES: u32 = 0xf2;
+pub const DEBUG_S_FILECHKSMS: u32 = 0xf4;
+pub const DEBUG_S_INLINEELINES: u32 = 0xf6;
+pub const DEBUG_S_CROSSSCOPEIMPORTS: u32 = 0xf7;
+pub const DEBUG_S_CROSSSCOPEEXPORTS: u32 = 0xf8;
+
+/// The signature of a `DEBUG_S_INLINEELINES` subsection with one file per inlinee.
+const CV_INLINEE_SOURCE_LINE_SIGNATURE: u32 = 0x0;
+/// The signature of a `DEBUG_S_INLINEELINES` subsection listing extra files for each inlinee.
+const CV_INLINEE_SOURCE_LINE_SIGNATURE_EX: u32 = 0x1;
+
+/// Set in the flags of a `DEBUG_S_LINES` subsection if its lines carry column information.
+const CV_LINES_HAVE_COLUMNS: u16 = 0x1;
+
+/// A single C13 debug subsection.
+#[derive(Debug,Copy,Clone)]
+pub struct DebugSubsection<'a> {
+    pub kind: u32,
+    pub data: &'a [u8],
+}
+
+/// Iterates over the subsections of a C13 line information area.
+#[derive(Debug)]
+pub struct DebugSubsectionIter<'a> {
+    buf: ParseBuffer<'a>,
+}
+
+impl<'a> DebugSubsectionIter<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        DebugSubsectionIter { buf: ParseBuffer::from(data) }
+    }
+}
+
+impl<'a> FallibleIterator for DebugSubsectionIter<'a> {
+    type Item = DebugSubsection<'a>;
+    type Error = Error;
+
+    fn next(&mut self) -> result::Result<Option<Self::Item>, Self::Error> {
+        while self.buf.len() > 0 {
+            let kind = self.buf.parse_u32()?;
+            let length = self.buf.parse_u32()? as usize;
+            let data = self.buf.take(length)?;
+
+            // subsections are padded to a multiple of four bytes, except perhaps the last one
+            if self.buf.len() > 0 {
+                self.buf.align(4)?;
+            }
+
+            if kind & DEBUG_S_IGNORE == 0 {
+                return Ok(Some(DebugSubsection { kind, data }));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Finds the first subsection of the given kind.
+pub fn find_subsection(data: &[u8], kind: u32) -> Result<Option<&[u8]>> {
+    let mut subsections = DebugSubsectionIter::new(data);
+    while let Some(subsection) = subsections.next()? {
+        if subsection.kind == kind {
+            return Ok(Some(subsection.data));
+        }
+    }
+    Ok(None)
+}
+
+/// Parses a `DEBUG_S_LINES` subsection into `LineInfo`s sorted by offset.
+///
+/// The subsection starts with a `CV_DebugSLinesHeader_t` describing a contiguous range of code,
+/// usually a function, followed by one `CV_DebugSLinesFileBlockHeader_t` per source file which
+/// contributed to that range. Each block holds `CV_Line_t` records and, if the header says so,
+/// the same number of `CV_Column_t` records.
+fn parse_lines(data: &[u8]) -> Result<Vec<LineInfo>> {
+    let mut buf = ParseBuffer::from(data);
+    let offset = buf.parse_u32()?;
+    let segment = buf.parse_u16()?;
+    let flags = buf.parse_u16()?;
+    let code_size = buf.parse_u32()?;
+    let have_columns = flags & CV_LINES_HAVE_COLUMNS != 0;
+
+    let mut lines = Vec::new();
+    while buf.len() > 0 {
+        let file_index = buf.parse_u32()?;
+        let line_count = buf.parse_u32()? as usize;
+        let _block_size = buf.parse_u32()?;
+
+        let first = lines.len();
+        for _ in 0..line_count {
+            let line_offset = buf.parse_u32()?;
+            let line_flags = buf.parse_u32()?;
+
+            // linenumStart:24, deltaLineEnd:7, fStatement:1
+            let line_start = line_flags & 0x00ff_ffff;
+            let delta = (line_flags >> 24) & 0x7f;
+            lines.push(LineInfo {
+                offset: offset.wrapping_add(line_offset),
+                segment,
+                length: 0,
+                file_index,
+                line_start,
+                line_end: line_start + delta,
+                column_start: None,
+                column_end: None,
+                kind: if line_flags & 0x8000_0000 != 0 {
+                    LineInfoKind::Statement
+                } else {
+                    LineInfoKind::Expression
+                },
+            });
+        }
+
+        if have_columns {
+            for line in &mut lines[first..] {
+                line.column_start = Some(buf.parse_u16()?);
+                line.column_end = Some(buf.parse_u16()?);
+            }
+        }
+    }
+
+    // each line extends to the next one, and the last one to the end of the range
+    lines.sort_by_key(|line| line.offset);
+    let end = offset.wrapping_add(code_size);
+    for i in 0..lines.len() {
+        let next = lines.get(i + 1).map(|next| next.offset).unwrap_or(end);
+        lines[i].length = next.wrapping_sub(lines[i].offset);
+    }
+
+    Ok(lines)
+}
+
+/// Iterates over the lines of every `DEBUG_S_LINES` subsection.
+#[derive(Debug)]
+pub struct LineIterator<'a> {
+    subsections: DebugSubsectionIter<'a>,
+    lines: vec::IntoIter<LineInfo>,
+}
+
+impl<'a> LineIterator<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        LineIterator {
+            subsections: DebugSubsectionIter::new(data),
+            lines: Vec::new().into_iter(),
+        }
+    }
+}
+
+impl<'a> FallibleIterator for LineIterator<'a> {
+    type Item = LineInfo;
+    type Error = Error;
+
+    fn next(&mut self) -> result::Result<Option<Self::Item>, Self::Error> {
+        loop {
+            if let Some(line) = self.lines.next() {
+                return Ok(Some(line));
+            }
+
+            match self.subsections.next()? {
+                Some(subsection) => {
+                    if subsection.kind == DEBUG_S_LINES {
+                        self.lines = parse_lines(subsection.data)?.into_iter();
+                    }
+                }
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+/// Parses the `DEBUG_S_FILECHKSMS` entry at the current position of `buf`, which must span the
+/// whole subsection so that the entry's offset is its `FileIndex`.
+///
+/// Each entry holds the `/names` offset of the file name, the size and kind of the checksum, and
+/// the checksum itself, padded to a multiple of four bytes.
+pub fn parse_file_checksum<'a>(buf: &mut ParseBuffer<'a>) -> Result<FileChecksum<'a>> {
+    let file_index = buf.pos() as FileIndex;
+    let name = buf.parse_u32()?;
+    let size = buf.parse_u8()? as usize;
+    let kind = buf.parse_u8()?.into();
+    let checksum = buf.take(size)?;
+
+    if buf.len() > 0 {
+        buf.align(4)?;
+    }
+
+    Ok(FileChecksum { file_index, name, kind, checksum })
+}
+
+/// Returns the `DEBUG_S_FILECHKSMS` entry at `file_index` bytes into that subsection.
+pub fn file_checksum(data: &[u8], file_index: FileIndex) -> Result<FileChecksum<'_>> {
+    let checksums = find_subsection(data, DEBUG_S_FILECHKSMS)?.ok_or(Error::UnexpectedEof)?;
+    if file_index as usize >= checksums.len() {
+        return Err(Error::UnexpectedEof);
+    }
+
+    let mut buf = ParseBuffer::from(checksums);
+    buf.take(file_index as usize)?;
+    parse_file_checksum(&mut buf)
+}
+
+/// Parses the `DEBUG_S_CROSSSCOPEIMPORTS` entry at the current position of `buf`.
+///
+/// Each entry holds the `/names` offset of the exporting module's name, followed by a count and
+/// the item indices imported from that module, which are local to that module.
+pub fn parse_cross_module_import(buf: &mut ParseBuffer) -> Result<CrossModuleImport> {
+    let module_name = buf.parse_u32()?;
+    let count = buf.parse_u32()?;
+
+    let mut local_indices = Vec::new();
+    for _ in 0..count {
+        local_indices.push(buf.parse_u32()?);
+    }
+
+    Ok(CrossModuleImport { module_name, local_indices })
+}
+
+/// Parses the `DEBUG_S_CROSSSCOPEEXPORTS` entry at the current position of `buf`, which is a
+/// pair of a local and a global item index.
+pub fn parse_cross_module_export(buf: &mut ParseBuffer) -> Result<CrossModuleExport> {
+    Ok(CrossModuleExport {
+        local: buf.parse_u32()?,
+        global: buf.parse_u32()?,
+    })
+}
+
+/// Iterates over the entries of every `DEBUG_S_INLINEELINES` subsection.
+///
+/// Each subsection starts with a signature, followed by one `InlineeSourceLine` per inlined
+/// function: its item index, file, and line. With `CV_INLINEE_SOURCE_LINE_SIGNATURE_EX`, each
+/// entry is followed by a count and the indices of further files the inlinee's code came from.
+#[derive(Debug)]
+pub struct InlineeLineIterator<'a> {
+    subsections: DebugSubsectionIter<'a>,
+    buf: ParseBuffer<'a>,
+    extended: bool,
+}
+
+impl<'a> InlineeLineIterator<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        InlineeLineIterator {
+            subsections: DebugSubsectionIter::new(data),
+            buf: ParseBuffer::from(&[][..]),
+            extended: false,
+        }
+    }
+}
+
+impl<'a> FallibleIterator for InlineeLineIterator<'a> {
+    type Item = InlineeLine;
+    type Error = Error;
+
+    fn next(&mut self) -> result::Result<Option<Self::Item>, Self::Error> {
+        while self.buf.len() == 0 {
+            match self.subsections.next()? {
+                Some(subsection) => {
+                    if subsection.kind == DEBUG_S_INLINEELINES {
+                        self.buf = ParseBuffer::from(subsection.data);
+                        self.extended = match self.buf.parse_u32()? {
+                            CV_INLINEE_SOURCE_LINE_SIGNATURE => false,
+                            CV_INLINEE_SOURCE_LINE_SIGNATURE_EX => true,
+                            _ => return Err(Error::UnimplementedFeature("unknown inlinee lines signature")),
+                        };
+                    }
+                }
+                None => return Ok(None),
+            }
+        }
+
+        let inlinee = self.buf.parse_u32()?;
+        let file_index = self.buf.parse_u32()?;
+        let line = self.buf.parse_u32()?;
+
+        let mut extra_files = Vec::new();
+        if self.extended {
+            let count = self.buf.parse_u32()?;
+            for _ in 0..count {
+                extra_files.push(self.buf.parse_u32()?);
+            }
+        }
+
+        Ok(Some(InlineeLine { inlinee, file_index, line, extra_files }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod lines {
+        use module_info::c13::*;
+        use module_info::{CrossModuleExport, CrossModuleImport, FileChecksumKind, InlineeLine};
+
+        fn push_u32(data: &mut Vec<u8>, value: u32) {
+            data.extend_from_slice(&[value as u8, (value >> 8) as u8,
+                                     (value >> 16) as u8, (value >> 24) as u8]);
+        }
+
+        fn subsection(kind: u32, data: &[u8]) -> Vec<u8> {
+            let mut result = Vec::new();
+            push_u32(&mut result, kind);
+            push_u32(&mut result, data.len() as u32);
+            result.extend_from_slice(data);
+            while result.len() % 4 != 0 {
+                result.push(0);
+            }
+            result
+        }
+
+        fn lines_subsection(have_columns: bool) -> Vec<u8> {
+            let mut data = Vec::new();
+            push_u32(&mut data, 0x100);                        // offCon
+            data.extend_from_slice(&[2, 0]);                   // segCon
+            data.extend_from_slice(&[have_columns as u8, 0]);  // flags
+            push_u32(&mut data, 0x30);                         // cbCon
+
+            // file block: two lines of the file at checksum offset 0x18
+            push_u32(&mut data, 0x18);
+            push_u32(&mut data, 2);
+            push_u32(&mut data, 12 + 2 * 8 + if have_columns { 2 * 4 } else { 0 });
+            push_u32(&mut data, 0);
+            push_u32(&mut data, 0x8000_0000 | 10);
+            push_u32(&mut data, 0x10);
+            push_u32(&mut data, 0x8000_0000 | (2 << 24) | 12);
+            if have_columns {
+                data.extend_from_slice(&[5, 0, 9, 0, 1, 0, 20, 0]);
+            }
+
+            // file block: one expression line at checksum offset 0 in between
+            push_u32(&mut data, 0);
+            push_u32(&mut data, 1);
+            push_u32(&mut data, 12 + 8 + if have_columns { 4 } else { 0 });
+            push_u32(&mut data, 0x8);
+            push_u32(&mut data, 50);
+            if have_columns {
+                data.extend_from_slice(&[3, 0, 4, 0]);
+            }
+
+            subsection(DEBUG_S_LINES, &data)
+        }
+
+        #[test]
+        fn test_parse_lines() {
+            let mut data = subsection(0xf4, &[0; 6]);
+            data.extend(subsection(DEBUG_S_IGNORE | DEBUG_S_LINES, &[0; 4]));
+            data.extend(lines_subsection(false));
+
+            let lines: Vec<LineInfo> = LineIterator::new(&data).collect().expect("collect");
+            assert_eq!(lines, vec![
+                LineInfo { offset: 0x100, segment: 2, length: 8, file_index: 0x18,
+                           line_start: 10, line_end: 10, column_start: None, column_end: None,
+                           kind: LineInfoKind::Statement },
+                LineInfo { offset: 0x108, segment: 2, length: 8, file_index: 0,
+                           line_start: 50, line_end: 50, column_start: None, column_end: None,
+                           kind: LineInfoKind::Expression },
+                LineInfo { offset: 0x110, segment: 2, length: 0x20, file_index: 0x18,
+                           line_start: 12, line_end: 14, column_start: None, column_end: None,
+                           kind: LineInfoKind::Statement },
+            ]);
+        }
+
+        #[test]
+        fn test_parse_columns() {
+            let data = lines_subsection(true);
+            let lines: Vec<LineInfo> = LineIterator::new(&data).collect().expect("collect");
+            let columns: Vec<_> = lines.iter().map(|l| (l.column_start, l.column_end)).collect();
+            assert_eq!(columns, vec![(Some(5), Some(9)), (Some(3), Some(4)), (Some(1), Some(20))]);
+        }
+
+        fn checksums() -> Vec<u8> {
+            let mut checksums = Vec::new();
+            push_u32(&mut checksums, 0x1234);
+            checksums.extend_from_slice(&[0, 0, 0, 0]);  // no checksum, padding
+            push_u32(&mut checksums, 0x5678);
+            checksums.extend_from_slice(&[4, 1, 0xde, 0xad, 0xbe, 0xef, 0, 0]);  // 4 byte "MD5"
+            push_u32(&mut checksums, 0x9abc);
+            checksums.extend_from_slice(&[1, 7, 0xff]);  // unknown kind, unpadded
+            subsection(DEBUG_S_FILECHKSMS, &checksums)
+        }
+
+        #[test]
+        fn test_file_checksum() {
+            let data = checksums();
+
+            let file = file_checksum(&data, 0).expect("file 0");
+            assert_eq!((file.file_index, file.name, file.kind), (0, 0x1234, FileChecksumKind::None));
+            assert_eq!(file.checksum, &[]);
+
+            let file = file_checksum(&data, 8).expect("file 8");
+            assert_eq!((file.file_index, file.name, file.kind), (8, 0x5678, FileChecksumKind::MD5));
+            assert_eq!(file.checksum, &[0xde, 0xad, 0xbe, 0xef]);
+
+            match file_checksum(&data, 28) {
+                Err(Error::UnexpectedEof) => (),
+                _ => panic!("expected UnexpectedEof"),
+            }
+        }
+
+        #[test]
+        fn test_inlinee_lines() {
+            let mut plain = Vec::new();
+            push_u32(&mut plain, CV_INLINEE_SOURCE_LINE_SIGNATURE);
+            for &value in &[0x1000, 0x18, 42, 0x1001, 0, 7] {
+                push_u32(&mut plain, value);
+            }
+
+            let mut extended = Vec::new();
+            push_u32(&mut extended, CV_INLINEE_SOURCE_LINE_SIGNATURE_EX);
+            for &value in &[0x1002, 0x30, 9, 2, 0x48, 0x60, 0x1003, 0x18, 1, 0] {
+                push_u32(&mut extended, value);
+            }
+
+            let mut data = subsection(DEBUG_S_INLINEELINES, &plain);
+            data.extend(lines_subsection(false));
+            data.extend(subsection(DEBUG_S_INLINEELINES, &extended));
+
+            let lines: Vec<InlineeLine> = InlineeLineIterator::new(&data).collect().expect("collect");
+            assert_eq!(lines, vec![
+                InlineeLine { inlinee: 0x1000, file_index: 0x18, line: 42, extra_files: vec![] },
+                InlineeLine { inlinee: 0x1001, file_index: 0, line: 7, extra_files: vec![] },
+                InlineeLine { inlinee: 0x1002, file_index: 0x30, line: 9, extra_files: vec![0x48, 0x60] },
+                InlineeLine { inlinee: 0x1003, file_index: 0x18, line: 1, extra_files: vec![] },
+            ]);
+        }
+
+        #[test]
+        fn test_inlinee_lines_signature() {
+            let data = subsection(DEBUG_S_INLINEELINES, &[2, 0, 0, 0]);
+            match InlineeLineIterator::new(&data).next() {
+                Err(Error::UnimplementedFeature(_)) => (),
+                _ => panic!("expected UnimplementedFeature"),
+            }
+
+            // a subsection holding just the signature has no entries
+            let data = subsection(DEBUG_S_INLINEELINES, &[0, 0, 0, 0]);
+            assert_eq!(InlineeLineIterator::new(&data).count().expect("count"), 0);
+        }
+
+        #[test]
+        fn test_cross_module_imports() {
+            let mut data = Vec::new();
+            for &value in &[0x10, 2, 0x1005, 0x1009, 0x20, 0, 0x30, 1, 0x1000] {
+                push_u32(&mut data, value);
+            }
+
+            let mut buf = ParseBuffer::from(data.as_slice());
+            let mut imports = Vec::new();
+            while buf.len() > 0 {
+                imports.push(parse_cross_module_import(&mut buf).expect("parse"));
+            }
+
+            assert_eq!(imports, vec![
+                CrossModuleImport { module_name: 0x10, local_indices: vec![0x1005, 0x1009] },
+                CrossModuleImport { module_name: 0x20, local_indices: vec![] },
+                CrossModuleImport { module_name: 0x30, local_indices: vec![0x1000] },
+            ]);
+
+            let mut buf = ParseBuffer::from(&data[..12]);
+            match parse_cross_module_import(&mut buf) {
+                Err(Error::UnexpectedEof) => (),
+                _ => panic!("expected UnexpectedEof"),
+            }
+        }
+
+        #[test]
+        fn test_cross_module_exports() {
+            let mut data = Vec::new();
+            for &value in &[0x1000, 0x2345, 0x1003, 0x2346] {
+                push_u32(&mut data, value);
+            }
+
+            let mut buf = ParseBuffer::from(data.as_slice());
+            assert_eq!(parse_cross_module_export(&mut buf).expect("parse"),
+                       CrossModuleExport { local: 0x1000, global: 0x2345 });
+            assert_eq!(parse_cross_module_export(&mut buf).expect("parse"),
+                       CrossModuleExport { local: 0x1003, global: 0x2346 });
+            assert_eq!(buf.len(), 0);
+        }
+
+        #[test]
+        fn test_parse_file_checksums() {
+            let data = checksums();
+            let subsection = find_subsection(&data, DEBUG_S_FILECHKSMS).expect("find").expect("some");
+
+            let mut buf = ParseBuffer::from(subsection);
+            let mut files = Vec::new();
+            while buf.len() > 0 {
+                files.push(parse_file_checksum(&mut buf).expect("parse"));
+            }
+
+            let summary: Vec<_> = files.iter().map(|f| (f.file_index, f.name, f.kind)).collect();
+            assert_eq!(summary, vec![
+                (0, 0x1234, FileChecksumKind::None),
+                (8, 0x5678, FileChecksumKind::MD5),
+                (20, 0x9abc, FileChecksumKind::OtherValue(7)),
+            ]);
+            assert_eq!(files[2].checksum, &[0xff]);
+        }
+    }
+}
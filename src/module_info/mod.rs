@@ -0,0 +1,765 @@
+use common::*;
+use dbi::Module;
+use msf::Stream;
+use std::mem;
+use std::result;
+use strings::StringTable;
+use symbol::constants::*;
+use symbol::{self, CompileFlags, CompilerVersion, CPUType, FrameProcedureSymbol, ScopedSymbolIter,
+             SourceLanguage, Symbol, SymbolData, SymbolIter, VariableLocation};
+use FallibleIterator;
+
+mod c11;
+mod c13;
+
+/// The signature at the start of a module information stream.
+const MODI_SIGNATURE: u32 = 4;
+
+/// The line information area of a module info stream, as a range of bytes within the stream.
+enum Lines {
+    None,
+    C11 { offset: usize, size: usize },
+    C13 { offset: usize, size: usize },
+}
+
+/// This struct contains data about a single module from its module info stream.
+///
+/// The module info stream is where private symbols and line info is stored.
+pub struct ModuleInfo<'m> {
+    stream: Stream<'m>,
+    symbols_size: usize,
+    lines: Lines,
+}
+
+/// Whether a line record marks the start of a statement or of an expression.
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub enum LineInfoKind {
+    /// The line marks the start of an expression within a statement.
+    Expression,
+    /// The line marks the start of a statement.
+    Statement,
+}
+
+/// Maps a range of code to a location in a source file.
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub struct LineInfo {
+    /// The offset of the first byte of code within the section given by `segment`.
+    pub offset: u32,
+    /// The section containing the code.
+    pub segment: u16,
+    /// The number of bytes of code covered by this line.
+    pub length: u32,
+    /// The source file, which can be resolved with `ModuleInfo::file_name()`.
+    pub file_index: FileIndex,
+    /// The first line of the source range.
+    pub line_start: u32,
+    /// The last line of the source range.
+    pub line_end: u32,
+    /// The first column of the source range, if columns were recorded.
+    pub column_start: Option<u16>,
+    /// The column just past the source range, if columns were recorded.
+    pub column_end: Option<u16>,
+    /// Whether this is the start of a statement or an expression.
+    pub kind: LineInfoKind,
+}
+
+/// The kind of checksum recorded for a source file.
+///
+/// Named `CV_SourceChksum_t` in `cvinfo.h`.
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub enum FileChecksumKind {
+    /// No checksum was recorded.
+    None,
+    /// A 16-byte MD5 digest.
+    MD5,
+    /// A 20-byte SHA-1 digest.
+    SHA1,
+    /// A 32-byte SHA-256 digest.
+    SHA256,
+    /// A kind of checksum not known to this crate, holding its raw `CV_SourceChksum_t` value.
+    OtherValue(u8),
+}
+
+impl From<u8> for FileChecksumKind {
+    fn from(v: u8) -> Self {
+        match v {
+            0 => FileChecksumKind::None,
+            1 => FileChecksumKind::MD5,
+            2 => FileChecksumKind::SHA1,
+            3 => FileChecksumKind::SHA256,
+            _ => FileChecksumKind::OtherValue(v),
+        }
+    }
+}
+
+/// A source file of a module, along with the checksum of its contents at compile time.
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub struct FileChecksum<'a> {
+    /// The index by which `LineInfo`s refer to this file.
+    pub file_index: FileIndex,
+    /// The offset of the file name in the PDB's string table.
+    pub name: u32,
+    /// The hash algorithm used to compute `checksum`.
+    pub kind: FileChecksumKind,
+    /// The checksum bytes, empty if `kind` is `FileChecksumKind::None`.
+    pub checksum: &'a [u8],
+}
+
+/// The source location at which the code of an inlined function begins.
+///
+/// Line numbers within an inlined function are recorded by the binary annotations of each
+/// `S_INLINESITE` symbol, relative to this location.
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub struct InlineeLine {
+    /// The `LF_FUNC_ID` or `LF_MFUNC_ID` record of the inlined function.
+    pub inlinee: ItemIndex,
+    /// The source file containing the start of the inlined function.
+    pub file_index: FileIndex,
+    /// The line at which the inlined function starts.
+    pub line: u32,
+    /// Further source files which contributed code to the inlined function, if recorded.
+    pub extra_files: Vec<FileIndex>,
+}
+
+/// The item indices a module imports from another module.
+///
+/// Item indices are normally global to the PDB. Incrementally linked PDBs can leave them local to
+/// each module instead, and modules then refer to items of other modules through a
+/// `CrossModuleRef` into their imports.
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub struct CrossModuleImport {
+    /// The offset of the exporting module's name in the PDB's string table.
+    pub module_name: u32,
+    /// The imported item indices, local to the exporting module.
+    pub local_indices: Vec<ItemIndex>,
+}
+
+/// Maps an item index local to a module to the corresponding global item index.
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub struct CrossModuleExport {
+    /// The item index within the exporting module.
+    pub local: ItemIndex,
+    /// The item index within the PDB's IPI stream.
+    pub global: ItemIndex,
+}
+
+/// A reference to an item of another module, encoded in an `ItemIndex`.
+///
+/// Cross-module references have the high bit set. The next 11 bits select an entry of the
+/// referring module's `cross_module_imports()`, and the low 20 bits select an item within that
+/// entry.
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub struct CrossModuleRef {
+    /// The index of the `CrossModuleImport` of the referring module.
+    pub module_index: usize,
+    /// The index into the `local_indices` of that import.
+    pub import_index: usize,
+}
+
+impl CrossModuleRef {
+    /// Decodes `index` as a cross-module reference, or returns `None` if it refers to an item
+    /// directly.
+    pub fn from_index(index: ItemIndex) -> Option<CrossModuleRef> {
+        if index & 0x8000_0000 == 0 {
+            return None;
+        }
+
+        Some(CrossModuleRef {
+            module_index: ((index >> 20) & 0x7ff) as usize,
+            import_index: (index & 0xf_ffff) as usize,
+        })
+    }
+}
+
+/// The item a `CrossModuleRef` resolves to.
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub struct CrossModuleTarget {
+    /// The offset of the exporting module's name in the PDB's string table.
+    pub module_name: u32,
+    /// The item index within the exporting module, which that module's
+    /// `ModuleInfo::global_index()` maps to a global item index.
+    pub local_index: ItemIndex,
+}
+
+/// A summary of how a module was compiled, produced by `ModuleInfo::compile_info()`.
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub struct CompileInfo<'a> {
+    /// The path of the object file the module was built from, if recorded.
+    pub object_name: Option<RawString<'a>>,
+    /// The name of the compiler, like `Microsoft (R) Optimizing Compiler`.
+    pub compiler_name: RawString<'a>,
+    /// The source language of the module.
+    pub language: SourceLanguage,
+    /// The CPU the module was compiled for.
+    pub cpu_type: CPUType,
+    /// The options the module was compiled with.
+    pub flags: CompileFlags,
+    /// The version of the compiler frontend, which parsed the source.
+    pub frontend_version: CompilerVersion,
+    /// The version of the compiler backend, which generated the code.
+    pub backend_version: CompilerVersion,
+    /// The `LF_BUILDINFO` record describing the build, if recorded.
+    pub build_info: Option<ItemIndex>,
+    /// The environment of the tool which produced the module, if recorded.
+    pub environment: Vec<(String, String)>,
+}
+
+impl<'m> ModuleInfo<'m> {
+    /// Get an iterator over the private symbols of this module.
+    pub fn symbols(&self) -> Result<SymbolIter<'_>> {
+        let mut buf = self.stream.parse_buffer();
+        buf.parse_u32()?;
+        let symbols = buf.take(self.symbols_size - mem::size_of::<u32>())?;
+        Ok(SymbolIter::new(symbols.into()))
+    }
+
+    /// Get an iterator over the private symbols of this module, along with the scopes they are in.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pdb::FallibleIterator;
+    /// #
+    /// # fn test() -> pdb::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb::PDB::open(file)?;
+    /// let dbi = pdb.debug_information()?;
+    /// let mut modules = dbi.modules()?;
+    /// if let Some(module) = modules.next()? {
+    ///     let info = pdb.module_info(&module)?;
+    ///     let mut symbols = info.scoped_symbols()?;
+    ///     while let Some(scoped) = symbols.next()? {
+    ///         println!("{:08x} {:indent$}{:?}", scoped.offset, "", scoped.symbol,
+    ///                  indent = scoped.depth * 2);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn scoped_symbols(&self) -> Result<ScopedSymbolIter<'_>> {
+        let mut buf = self.stream.parse_buffer();
+        buf.parse_u32()?;
+        let symbols = buf.take(self.symbols_size - mem::size_of::<u32>())?;
+        Ok(ScopedSymbolIter::new(symbols.into(), mem::size_of::<u32>() as u32))
+    }
+
+    /// Returns the symbol at `offset` bytes into the module info stream.
+    ///
+    /// Symbols refer to each other by such offsets, as do the `ProcedureReferenceSymbol`s in the
+    /// global symbol table. `offset` must be the start of a symbol; this is not checked, so other
+    /// offsets return whatever record the bytes there happen to describe.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::UnexpectedEof` if `offset` lies outside of the module's symbols
+    pub fn symbol_at(&self, offset: u32) -> Result<Symbol<'_>> {
+        SymbolIter::new(self.symbol_buffer_at(offset)?).next()?.ok_or(Error::UnexpectedEof)
+    }
+
+    /// Finds the `S_FRAMEPROC` symbol describing the stack frame of the procedure at `procedure`
+    /// bytes into the module info stream.
+    ///
+    /// Returns `None` if the procedure has no `S_FRAMEPROC`, which is common for procedures
+    /// without a stack frame of their own.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::UnexpectedSymbolKind` if `procedure` does not point to a procedure symbol
+    /// * `Error::UnexpectedEof` if `procedure` lies outside of the module's symbols
+    /// * `Error::SymbolTooShort` if the procedure or its `S_FRAMEPROC` symbol is truncated
+    pub fn frame_procedure(&self, procedure: u32) -> Result<Option<FrameProcedureSymbol>> {
+        let mut symbols = ScopedSymbolIter::new(self.symbol_buffer_at(procedure)?, procedure);
+
+        let start = symbols.next()?.ok_or(Error::UnexpectedEof)?.symbol;
+        match start.raw_kind() {
+            S_LPROC32 | S_LPROC32_ST | S_GPROC32 | S_GPROC32_ST |
+            S_LPROC32_ID | S_GPROC32_ID | S_LPROC32_DPC | S_LPROC32_DPC_ID => {
+                start.parse()?;
+            }
+            kind => return Err(Error::UnexpectedSymbolKind(kind)),
+        }
+
+        while let Some(scoped) = symbols.next()? {
+            // the symbol closing the procedure's scope
+            if scoped.depth == 0 {
+                break;
+            }
+
+            // the frame belongs to the procedure itself, so skip nested blocks and inline sites
+            if scoped.depth != 1 {
+                continue;
+            }
+
+            match scoped.symbol.parse() {
+                Ok(SymbolData::FrameProcedure(frame)) => return Ok(Some(frame)),
+                Ok(_) | Err(Error::UnimplementedSymbolKind(_)) => (),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Finds where the local variable described by the `S_LOCAL` symbol at `offset` lives at the
+    /// code address `segment:offset`.
+    ///
+    /// Optimized code describes the location of a local variable with the `S_DEFRANGE_*` symbols
+    /// following its `S_LOCAL`, each of which applies to a range of code. Locations which only
+    /// describe a part of the variable are ignored.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::UnexpectedSymbolKind` if `local` does not point to an `S_LOCAL` symbol
+    /// * `Error::UnexpectedEof` if `local` lies outside of the module's symbols
+    pub fn variable_location(&self, local: u32, segment: u16, offset: u32) -> Result<VariableLocation> {
+        symbol::locate_variable(SymbolIter::new(self.symbol_buffer_at(local)?), segment, offset)
+    }
+
+    /// Returns a summary of how this module was compiled, from its `S_OBJNAME`, `S_COMPILE2` or
+    /// `S_COMPILE3`, `S_ENVBLOCK`, and `S_BUILDINFO` symbols.
+    ///
+    /// Other symbols are skipped without being parsed. Unless the module records all of these
+    /// symbols, this scans all of its symbols.
+    ///
+    /// Returns `None` if the module has no `S_COMPILE2` or `S_COMPILE3` symbol. Modules combining
+    /// several object files, like those of import libraries, are described by their first one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pdb::FallibleIterator;
+    /// #
+    /// # fn test() -> pdb::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb::PDB::open(file)?;
+    /// let dbi = pdb.debug_information()?;
+    /// let mut modules = dbi.modules()?;
+    /// while let Some(module) = modules.next()? {
+    ///     let info = pdb.module_info(&module)?;
+    ///     if let Some(compile_info) = info.compile_info()? {
+    ///         println!("{}: {} {}", module.module_name(), compile_info.compiler_name,
+    ///                  compile_info.backend_version);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn compile_info(&self) -> Result<Option<CompileInfo<'_>>> {
+        let mut object_name = None;
+        let mut compile = None;
+        let mut build_info = None;
+        let mut environment = None;
+
+        let mut symbols = self.symbols()?;
+        while let Some(symbol) = symbols.next()? {
+            match symbol.raw_kind() {
+                S_OBJNAME | S_OBJNAME_ST |
+                S_COMPILE2 | S_COMPILE2_ST | S_COMPILE3 |
+                S_ENVBLOCK | S_BUILDINFO => (),
+                _ => continue,
+            }
+
+            match symbol.parse()? {
+                SymbolData::ObjName(_) if object_name.is_none() => object_name = Some(symbol.name()?),
+                SymbolData::CompileFlags(flags) if compile.is_none() => compile = Some((flags, symbol.name()?)),
+                SymbolData::EnvBlock(block) if environment.is_none() => {
+                    environment = Some(block.entries()
+                        .map(|(key, value)| (key.to_string().into_owned(), value.to_string().into_owned()))
+                        .collect()?);
+                }
+                SymbolData::BuildInfo(info) if build_info.is_none() => build_info = Some(info.id),
+                _ => (),
+            }
+
+            // these usually come first, but `S_BUILDINFO` and `S_ENVBLOCK` may follow the
+            // module's procedures, so only stop early once everything has been found
+            if compile.is_some() && build_info.is_some() && environment.is_some() {
+                break;
+            }
+        }
+
+        Ok(compile.map(|(flags, compiler_name)| CompileInfo {
+            object_name,
+            compiler_name,
+            language: flags.language,
+            cpu_type: flags.cpu_type,
+            flags: flags.flags,
+            frontend_version: flags.frontend_version,
+            backend_version: flags.backend_version,
+            build_info,
+            environment: environment.unwrap_or_default(),
+        }))
+    }
+
+    /// Returns the symbols starting `offset` bytes into the module info stream.
+    fn symbol_buffer_at(&self, offset: u32) -> Result<ParseBuffer<'_>> {
+        let mut buf = self.stream.parse_buffer();
+        let symbols = buf.take(self.symbols_size)?;
+
+        let offset = offset as usize;
+        if offset < mem::size_of::<u32>() || offset >= symbols.len() {
+            return Err(Error::UnexpectedEof);
+        }
+
+        Ok(ParseBuffer::from(&symbols[offset..]))
+    }
+
+    /// Get an iterator over the line information of this module.
+    ///
+    /// Both the current C13 format and the older C11 format are supported, and produce the same
+    /// `LineInfo` records, though C11 records carry no columns. Lines are produced grouped by
+    /// function (C13) or by file and segment (C11), and sorted by offset within each group.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pdb::FallibleIterator;
+    /// #
+    /// # fn test() -> pdb::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb::PDB::open(file)?;
+    /// let strings = pdb.string_table()?;
+    /// let dbi = pdb.debug_information()?;
+    /// let mut modules = dbi.modules()?;
+    /// if let Some(module) = modules.next()? {
+    ///     let info = pdb.module_info(&module)?;
+    ///     let mut lines = info.lines()?;
+    ///     while let Some(line) = lines.next()? {
+    ///         println!("{:x}:{:08x} {}:{}", line.segment, line.offset,
+    ///                  info.file_name(line.file_index, &strings)?, line.line_start);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn lines(&self) -> Result<LineIter<'_>> {
+        let inner = match self.lines {
+            Lines::None => LineIterInner::Empty,
+            Lines::C11 { offset, size } => {
+                LineIterInner::C11(c11::LineIterator::new(self.lines_data(offset, size)?)?)
+            }
+            Lines::C13 { offset, size } => {
+                LineIterInner::C13(c13::LineIterator::new(self.lines_data(offset, size)?))
+            }
+        };
+
+        Ok(LineIter { inner })
+    }
+
+    /// Resolves the name of the source file referred to by a `LineInfo`.
+    ///
+    /// C13 line information stores file names in the PDB's global string table, which is why
+    /// `strings` is needed. Older C11 line information stores them in the module itself.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::UnexpectedEof` if `file_index` does not refer to a file of this module
+    pub fn file_name<'a>(&'a self, file_index: FileIndex, strings: &'a StringTable) -> Result<RawString<'a>> {
+        self.resolve_file_name(file_index, Some(strings))
+    }
+
+    /// Like `file_name()`, but for PDBs which may lack a string table. This fails with
+    /// `Error::StreamNameNotFound` for C13 line information if `strings` is `None`.
+    pub(crate) fn resolve_file_name<'a>(&'a self, file_index: FileIndex, strings: Option<&'a StringTable>)
+        -> Result<RawString<'a>>
+    {
+        match self.lines {
+            Lines::C13 { offset, size } => {
+                let name = c13::file_checksum(self.lines_data(offset, size)?, file_index)?.name;
+                strings.ok_or(Error::StreamNameNotFound)?.get(name)
+            }
+            Lines::C11 { offset, size } => c11::file_name(self.lines_data(offset, size)?, file_index),
+            Lines::None => Err(Error::UnexpectedEof),
+        }
+    }
+
+    /// Get an iterator over the source files of this module and their checksums.
+    ///
+    /// Checksums are only recorded by C13 line information. For modules with C11 line
+    /// information or none at all, the iterator is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pdb::FallibleIterator;
+    /// #
+    /// # fn test() -> pdb::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb::PDB::open(file)?;
+    /// let strings = pdb.string_table()?;
+    /// let dbi = pdb.debug_information()?;
+    /// let mut modules = dbi.modules()?;
+    /// if let Some(module) = modules.next()? {
+    ///     let info = pdb.module_info(&module)?;
+    ///     let mut files = info.file_checksums()?;
+    ///     while let Some(file) = files.next()? {
+    ///         println!("{} {:?} {:?}", strings.get(file.name)?, file.kind, file.checksum);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn file_checksums(&self) -> Result<FileChecksumIter<'_>> {
+        let data = self.c13_subsection(c13::DEBUG_S_FILECHKSMS)?;
+        Ok(FileChecksumIter { buf: ParseBuffer::from(data) })
+    }
+
+    /// Returns the checksum of the source file referred to by a `LineInfo`.
+    ///
+    /// Returns `Ok(None)` if the module has C11 line information or none at all.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::UnexpectedEof` if `file_index` does not refer to a file of this module
+    pub fn file_checksum(&self, file_index: FileIndex) -> Result<Option<FileChecksum<'_>>> {
+        match self.lines {
+            Lines::C13 { offset, size } => {
+                Ok(Some(c13::file_checksum(self.lines_data(offset, size)?, file_index)?))
+            }
+            Lines::C11 { .. } | Lines::None => Ok(None),
+        }
+    }
+
+    /// Get an iterator over the source locations of the functions inlined into this module.
+    ///
+    /// Inlinee lines are only recorded by C13 line information. For modules with C11 line
+    /// information or none at all, the iterator is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use pdb::FallibleIterator;
+    /// #
+    /// # fn test() -> pdb::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb::PDB::open(file)?;
+    /// let dbi = pdb.debug_information()?;
+    /// let mut modules = dbi.modules()?;
+    /// if let Some(module) = modules.next()? {
+    ///     let info = pdb.module_info(&module)?;
+    ///     let inlinees: HashMap<pdb::ItemIndex, pdb::InlineeLine> = info.inlinee_lines()?
+    ///         .map(|line| (line.inlinee, line))
+    ///         .collect()?;
+    ///     println!("{} inlined functions", inlinees.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn inlinee_lines(&self) -> Result<InlineeLineIter<'_>> {
+        let data = match self.lines {
+            Lines::C13 { offset, size } => self.lines_data(offset, size)?,
+            Lines::C11 { .. } | Lines::None => &[],
+        };
+
+        Ok(InlineeLineIter { inner: c13::InlineeLineIterator::new(data) })
+    }
+
+    /// Get an iterator over the items this module imports from other modules.
+    ///
+    /// The position of each `CrossModuleImport` is its `CrossModuleRef::module_index`. The
+    /// iterator is empty unless the module's item indices are local to the module.
+    pub fn cross_module_imports(&self) -> Result<CrossModuleImportIter<'_>> {
+        let data = self.c13_subsection(c13::DEBUG_S_CROSSSCOPEIMPORTS)?;
+        Ok(CrossModuleImportIter { buf: ParseBuffer::from(data) })
+    }
+
+    /// Get an iterator over the items this module exports to other modules.
+    ///
+    /// The iterator is empty unless the module's item indices are local to the module.
+    pub fn cross_module_exports(&self) -> Result<CrossModuleExportIter<'_>> {
+        let data = self.c13_subsection(c13::DEBUG_S_CROSSSCOPEEXPORTS)?;
+        Ok(CrossModuleExportIter { buf: ParseBuffer::from(data) })
+    }
+
+    /// Resolves a reference to an item of another module, found in an item index of this module.
+    ///
+    /// `PDB::cross_module_target()` goes on to find the module the target belongs to. Passing
+    /// `local_index` to that module's `global_index()` yields the global index of the target.
+    ///
+    /// Returns `Ok(None)` if this module does not import the referenced item.
+    pub fn resolve_cross_module_ref(&self, reference: CrossModuleRef) -> Result<Option<CrossModuleTarget>> {
+        let import = match self.cross_module_imports()?.nth(reference.module_index)? {
+            Some(import) => import,
+            None => return Ok(None),
+        };
+
+        Ok(import.local_indices.get(reference.import_index).map(|&local_index| CrossModuleTarget {
+            module_name: import.module_name,
+            local_index,
+        }))
+    }
+
+    /// Maps an item index local to this module to the global item index it exports as.
+    ///
+    /// Returns `Ok(None)` if this module does not export `local`.
+    pub fn global_index(&self, local: ItemIndex) -> Result<Option<ItemIndex>> {
+        let export = self.cross_module_exports()?.find(|export| export.local == local)?;
+        Ok(export.map(|export| export.global))
+    }
+
+    /// Returns the data of the first C13 subsection of the given kind, or an empty slice if there
+    /// is no such subsection.
+    fn c13_subsection(&self, kind: u32) -> Result<&[u8]> {
+        match self.lines {
+            Lines::C13 { offset, size } => {
+                Ok(c13::find_subsection(self.lines_data(offset, size)?, kind)?.unwrap_or(&[]))
+            }
+            Lines::C11 { .. } | Lines::None => Ok(&[]),
+        }
+    }
+
+    fn lines_data(&self, offset: usize, size: usize) -> Result<&[u8]> {
+        let mut buf = self.stream.parse_buffer();
+        buf.take(offset)?;
+        buf.take(size)
+    }
+}
+
+/// An iterator over the `LineInfo`s of a module, produced by `ModuleInfo::lines()`.
+#[derive(Debug)]
+pub struct LineIter<'a> {
+    inner: LineIterInner<'a>,
+}
+
+#[derive(Debug)]
+enum LineIterInner<'a> {
+    Empty,
+    C11(c11::LineIterator<'a>),
+    C13(c13::LineIterator<'a>),
+}
+
+impl<'a> FallibleIterator for LineIter<'a> {
+    type Item = LineInfo;
+    type Error = Error;
+
+    fn next(&mut self) -> result::Result<Option<Self::Item>, Self::Error> {
+        match self.inner {
+            LineIterInner::Empty => Ok(None),
+            LineIterInner::C11(ref mut iter) => iter.next(),
+            LineIterInner::C13(ref mut iter) => iter.next(),
+        }
+    }
+}
+
+/// An iterator over the `FileChecksum`s of a module, produced by `ModuleInfo::file_checksums()`.
+#[derive(Debug)]
+pub struct FileChecksumIter<'a> {
+    buf: ParseBuffer<'a>,
+}
+
+impl<'a> FallibleIterator for FileChecksumIter<'a> {
+    type Item = FileChecksum<'a>;
+    type Error = Error;
+
+    fn next(&mut self) -> result::Result<Option<Self::Item>, Self::Error> {
+        if self.buf.len() == 0 {
+            return Ok(None);
+        }
+
+        c13::parse_file_checksum(&mut self.buf).map(Some)
+    }
+}
+
+/// An iterator over the `CrossModuleImport`s of a module, produced by
+/// `ModuleInfo::cross_module_imports()`.
+#[derive(Debug)]
+pub struct CrossModuleImportIter<'a> {
+    buf: ParseBuffer<'a>,
+}
+
+impl<'a> FallibleIterator for CrossModuleImportIter<'a> {
+    type Item = CrossModuleImport;
+    type Error = Error;
+
+    fn next(&mut self) -> result::Result<Option<Self::Item>, Self::Error> {
+        if self.buf.len() == 0 {
+            return Ok(None);
+        }
+
+        c13::parse_cross_module_import(&mut self.buf).map(Some)
+    }
+}
+
+/// An iterator over the `CrossModuleExport`s of a module, produced by
+/// `ModuleInfo::cross_module_exports()`.
+#[derive(Debug)]
+pub struct CrossModuleExportIter<'a> {
+    buf: ParseBuffer<'a>,
+}
+
+impl<'a> FallibleIterator for CrossModuleExportIter<'a> {
+    type Item = CrossModuleExport;
+    type Error = Error;
+
+    fn next(&mut self) -> result::Result<Option<Self::Item>, Self::Error> {
+        if self.buf.len() == 0 {
+            return Ok(None);
+        }
+
+        c13::parse_cross_module_export(&mut self.buf).map(Some)
+    }
+}
+
+/// An iterator over the `InlineeLine`s of a module, produced by `ModuleInfo::inlinee_lines()`.
+#[derive(Debug)]
+pub struct InlineeLineIter<'a> {
+    inner: c13::InlineeLineIterator<'a>,
+}
+
+impl<'a> FallibleIterator for InlineeLineIter<'a> {
+    type Item = InlineeLine;
+    type Error = Error;
+
+    fn next(&mut self) -> result::Result<Option<Self::Item>, Self::Error> {
+        self.inner.next()
+    }
+}
+
+pub fn new_module_info<'s, 'm>(stream: Stream<'s>, module: &Module<'m>) -> Result<ModuleInfo<'s>> {
+    let info = module.info();
+    {
+        let mut buf = stream.parse_buffer();
+        if buf.parse_u32()? != MODI_SIGNATURE {
+            return Err(Error::UnimplementedFeature("Unsupported module info format"));
+        }
+    }
+    let symbols_size = info.symbols_size as usize;
+    // the C11 lines follow the symbols, and the C13 lines follow those
+    let lines = if info.c13_lines_size > 0 {
+        Lines::C13 {
+            offset: symbols_size + info.lines_size as usize,
+            size: info.c13_lines_size as usize,
+        }
+    } else if info.lines_size > 0 {
+        Lines::C11 {
+            offset: symbols_size,
+            size: info.lines_size as usize,
+        }
+    } else {
+        Lines::None
+    };
+    Ok(ModuleInfo {
+        stream,
+        symbols_size,
+        lines,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    mod cross_module {
+        use module_info::*;
+
+        #[test]
+        fn test_cross_module_ref() {
+            assert_eq!(CrossModuleRef::from_index(0x1000), None);
+            assert_eq!(CrossModuleRef::from_index(0x8000_0000),
+                       Some(CrossModuleRef { module_index: 0, import_index: 0 }));
+            assert_eq!(CrossModuleRef::from_index(0x8030_0005),
+                       Some(CrossModuleRef { module_index: 3, import_index: 5 }));
+            assert_eq!(CrossModuleRef::from_index(0xffff_ffff),
+                       Some(CrossModuleRef { module_index: 0x7ff, import_index: 0xf_ffff }));
+        }
+    }
+}
@@ -304,8 +304,186 @@ mod big {
 }
 
 mod small {
+    use super::*;
+
     pub const MAGIC: &'static [u8] = b"Microsoft C/C++ program database 2.00\r\n\x1a\x4a\x47";
-    // TODO: implement SmallMSF
+
+    /// The PDB 2.00 header as stored on disk.
+    /// See the Microsoft code for reference: https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/PDB/msf/msf.cpp
+    #[derive(Debug, Pread)]
+    #[repr(C, packed)]
+    struct RawHeader {
+        magic: [u8; 44],
+        page_size: u32,
+        free_page_map: u16,
+        pages_used: u16,
+        directory_size: u32,
+        _reserved: u32,
+    }
+
+    /// The original multi-stream file format, used by Visual C++ 6.0 and earlier.
+    ///
+    /// `SmallMSF` differs from `BigMSF` in that page numbers are 16 bits wide and that the stream
+    /// table is located directly by the header rather than through a page list.
+    #[derive(Debug)]
+    pub struct SmallMSF<'s, S> {
+        header: Header,
+        source: S,
+        stream_table: StreamTable<'s>,
+    }
+
+    impl<'s, S: Source<'s>> SmallMSF<'s, S> {
+        pub fn new(source: S, header_view: Box<dyn SourceView<'s>>) -> Result<SmallMSF<'s, S>> {
+            let mut buf = ParseBuffer::from(header_view.as_slice());
+            let header: RawHeader = buf.parse()?;
+
+            if &header.magic[0..MAGIC.len()] != MAGIC {
+                return Err(Error::UnrecognizedFileFormat);
+            }
+
+            if header.page_size.count_ones() != 1 || header.page_size < 0x100
+                || header.page_size > 0x10000 {
+                    return Err(Error::InvalidPageSize(header.page_size));
+                }
+
+            let header_object = Header{
+                page_size: header.page_size as usize,
+                maximum_valid_page_number: header.pages_used as PageNumber,
+            };
+
+            // the page numbers of the stream table immediately follow the header
+            let size_of_stream_table_in_pages = header_object.pages_needed_to_store(header.directory_size as usize);
+            let mut stream_table_page_list = PageList::new(header_object.page_size);
+            for _ in 0..size_of_stream_table_in_pages {
+                let n = buf.parse_u16()?;
+                stream_table_page_list.push(header_object.validate_page_number(n as u32)?);
+            }
+
+            // truncate the stream table location to the correct size
+            stream_table_page_list.truncate(header.directory_size as usize);
+
+            Ok(SmallMSF{
+                header: header_object,
+                source,
+                stream_table: StreamTable::TableFound {
+                    stream_table_location: stream_table_page_list,
+                },
+            })
+        }
+
+        fn make_stream_table_available(&mut self) -> Result<()> {
+            // do we need to map the stream table itself?
+            let mut new_stream_table: Option<StreamTable> = None;
+            if let StreamTable::TableFound { ref stream_table_location } = self.stream_table {
+                let stream_table_view = view(&mut self.source, stream_table_location)?;
+                new_stream_table = Some(StreamTable::Available { stream_table_view });
+            }
+
+            if let Some(st) = new_stream_table {
+                self.stream_table = st;
+            }
+
+            Ok(())
+        }
+
+        fn look_up_stream(&mut self, stream_number: u32) -> Result<PageList> {
+            // ensure the stream table is available
+            self.make_stream_table_available()?;
+
+            let header = self.header;
+
+            // declare the things we're going to find
+            let bytes_in_stream: u32;
+            let page_list: PageList;
+
+            if let StreamTable::Available { ref stream_table_view } = self.stream_table {
+                let stream_table_slice = stream_table_view.as_slice();
+                let mut stream_table = ParseBuffer::from(stream_table_slice);
+
+                // the stream table is structured as:
+                // stream_count: u16, padding: u16
+                // 0..stream_count: size of stream in bytes (0xffffffff indicating "stream does not
+                //                  exist"), followed by a u32 which was a pointer in memory
+                // stream 0: u16 PageNumber
+                // stream 1: u16 PageNumber, u16 PageNumber
+                // (number of pages determined by number of bytes)
+
+                let stream_count = stream_table.parse_u16()? as u32;
+                stream_table.parse_u16()?;
+
+                // check if we've already outworn our welcome
+                if stream_number >= stream_count {
+                    return Err(Error::StreamNotFound(stream_number))
+                }
+
+                // walk over the streams before the requested stream
+                let mut page_numbers_to_skip: usize = 0;
+                for _ in 0..stream_number {
+                    let bytes = stream_table.parse_u32()?;
+                    stream_table.parse_u32()?;
+                    if bytes == 0xffffffff {
+                        // stream is not present, ergo nothing to skip
+                    } else {
+                        page_numbers_to_skip += header.pages_needed_to_store(bytes as usize);
+                    }
+                }
+
+                // read our stream's size
+                bytes_in_stream = stream_table.parse_u32()?;
+                stream_table.parse_u32()?;
+                if bytes_in_stream == 0xffffffff {
+                    return Err(Error::StreamNotFound(stream_number))
+                }
+                let pages_in_stream = header.pages_needed_to_store(bytes_in_stream as usize);
+
+                // skip the remaining streams' byte counts
+                let _ = stream_table.take((stream_count - stream_number - 1) as usize * 8)?;
+
+                // skip the preceding streams' page numbers
+                let _ = stream_table.take(page_numbers_to_skip * 2)?;
+
+                // we're now at the list of pages for our stream
+                // accumulate them into a PageList
+                let mut list = PageList::new(header.page_size);
+                for _ in 0..pages_in_stream {
+                    let page_number = stream_table.parse_u16()?;
+                    list.push(self.header.validate_page_number(page_number as u32)?);
+                }
+
+                // truncate to the size of the stream
+                list.truncate(bytes_in_stream as usize);
+
+                page_list = list;
+            } else {
+                unreachable!();
+            }
+
+            // done!
+            Ok(page_list)
+        }
+    }
+
+    impl<'s, S: Source<'s>> MSF<'s, S> for SmallMSF<'s, S> {
+        fn get(&mut self, stream_number: u32, limit: Option<usize>) -> Result<Stream<'s>> {
+            // look up the stream
+            let mut page_list = self.look_up_stream(stream_number)?;
+
+            // apply any limits we have
+            if let Some(limit) = limit {
+                page_list.truncate(limit);
+            }
+
+            // now that we know where this stream lives, we can view it
+            let view = view(&mut self.source, &page_list)?;
+
+            // pack it into a Stream
+            let stream = Stream {
+                source_view: view,
+            };
+
+            Ok(stream)
+        }
+    }
 }
 
 /// Represents a single Stream within the multi-stream file.
@@ -346,8 +524,9 @@ pub fn open_msf<'s, S: Source<'s> + 's>(mut source: S) -> Result<Box<MSF<'s, S>
     }
 
     if header_matches(header_view.as_slice(), small::MAGIC) {
-        // sorry
-        return Err(Error::UnimplementedFeature("small MSF file format"));
+        // claimed!
+        let smallmsf = small::SmallMSF::new(source, header_view)?;
+        return Ok(Box::new(smallmsf))
     }
 
     Err(Error::UnrecognizedFileFormat)
@@ -390,4 +569,108 @@ mod tests {
             assert!(match h.validate_page_number(17) { Err(Error::PageReferenceOutOfRange(17)) => true, _ => false });
         }
     }
+
+    mod small_msf {
+        use std::io::Cursor;
+        use common::Error;
+        use msf::{open_msf, small};
+
+        fn put_u16(data: &mut [u8], offset: usize, value: u16) {
+            data[offset] = value as u8;
+            data[offset + 1] = (value >> 8) as u8;
+        }
+
+        fn put_u32(data: &mut [u8], offset: usize, value: u32) {
+            put_u16(data, offset, value as u16);
+            put_u16(data, offset + 2, (value >> 16) as u16);
+        }
+
+        /// Build a six page PDB 2.00 file with a page size of 0x400 containing four streams:
+        ///
+        /// * stream 0 is empty
+        /// * stream 1 contains "hello" on page 4
+        /// * stream 2 does not exist
+        /// * stream 3 contains 1500 bytes spread over pages 5 and 3
+        fn small_msf_file() -> Vec<u8> {
+            let page_size = 0x400;
+            let mut data = vec![0u8; page_size * 6];
+
+            // header
+            data[0..small::MAGIC.len()].copy_from_slice(small::MAGIC);
+            put_u32(&mut data, 44, page_size as u32);
+            put_u16(&mut data, 48, 1);
+            put_u16(&mut data, 50, 6);
+            put_u32(&mut data, 52, 4 + 4 * 8 + 3 * 2);
+            put_u16(&mut data, 60, 2);
+
+            // stream table
+            let st = 2 * page_size;
+            put_u16(&mut data, st, 4);
+            put_u32(&mut data, st + 4, 0);
+            put_u32(&mut data, st + 12, 5);
+            put_u32(&mut data, st + 20, 0xffffffff);
+            put_u32(&mut data, st + 28, 1500);
+            put_u16(&mut data, st + 36, 4);
+            put_u16(&mut data, st + 38, 5);
+            put_u16(&mut data, st + 40, 3);
+
+            // stream contents
+            data[4 * page_size..4 * page_size + 5].copy_from_slice(b"hello");
+            for i in 0..page_size {
+                data[5 * page_size + i] = 1;
+            }
+            for i in 0..(1500 - page_size) {
+                data[3 * page_size + i] = 2;
+            }
+
+            data
+        }
+
+        #[test]
+        fn test_streams() {
+            let mut msf = open_msf(Cursor::new(small_msf_file())).expect("open small MSF");
+
+            let stream = msf.get(0, None).expect("stream 0");
+            assert_eq!(stream.parse_buffer().len(), 0);
+
+            let stream = msf.get(1, None).expect("stream 1");
+            let mut buf = stream.parse_buffer();
+            assert_eq!(buf.take(5).expect("take"), b"hello");
+            assert_eq!(buf.len(), 0);
+
+            let stream = msf.get(3, None).expect("stream 3");
+            let mut buf = stream.parse_buffer();
+            assert_eq!(buf.len(), 1500);
+            assert!(buf.take(0x400).expect("take").iter().all(|b| *b == 1));
+            assert!(buf.take(1500 - 0x400).expect("take").iter().all(|b| *b == 2));
+
+            let stream = msf.get(3, Some(10)).expect("stream 3 with limit");
+            assert_eq!(stream.parse_buffer().len(), 10);
+        }
+
+        #[test]
+        fn test_missing_streams() {
+            let mut msf = open_msf(Cursor::new(small_msf_file())).expect("open small MSF");
+            match msf.get(2, None) {
+                Err(Error::StreamNotFound(2)) => (),
+                _ => panic!("expected StreamNotFound(2)")
+            }
+            match msf.get(4, None) {
+                Err(Error::StreamNotFound(4)) => (),
+                _ => panic!("expected StreamNotFound(4)")
+            }
+        }
+
+        #[test]
+        fn test_page_reference_out_of_range() {
+            let mut data = small_msf_file();
+            // point stream 1 past the last page
+            put_u16(&mut data, 2 * 0x400 + 36, 7);
+            let mut msf = open_msf(Cursor::new(data)).expect("open small MSF");
+            match msf.get(1, None) {
+                Err(Error::PageReferenceOutOfRange(7)) => (),
+                _ => panic!("expected PageReferenceOutOfRange(7)")
+            }
+        }
+    }
 }
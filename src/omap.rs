@@ -0,0 +1,308 @@
+// Copyright 2017 pdb Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Address translation for executables rewritten after linking.
+//!
+//! Post-link optimizers like BBT rearrange the code and data of an executable after the PDB was
+//! written. Rather than rewriting every address in the PDB, they record OMAP tables which map
+//! addresses between the original layout and the transformed layout. Symbols continue to refer
+//! to the original layout.
+
+use std::cmp;
+
+use common::*;
+use pe::ImageSectionHeader;
+use symbol::SymbolData;
+
+/// A single OMAP record, mapping the start of a range of addresses to a new location.
+///
+/// Named `OMAP` in the DIA SDK documentation:
+/// https://docs.microsoft.com/en-us/visualstudio/debugger/debug-interface-access/idiaenumdebugstreamdata
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub struct OMAPRecord {
+    /// The first RVA of the range in the source layout.
+    pub source_address: u32,
+
+    /// The RVA the range moved to in the target layout, or `0` if the range was removed.
+    pub target_address: u32,
+}
+
+/// A table of `OMAPRecord`s mapping addresses in one layout to addresses in another.
+///
+/// Each record covers the addresses from its `source_address` up to the next record's
+/// `source_address`.
+#[derive(Debug,Clone,Default)]
+pub struct OMAPTable {
+    records: Vec<OMAPRecord>,
+}
+
+impl OMAPTable {
+    /// Parses an OMAP stream, which is a series of `OMAPRecord`s sorted by `source_address`.
+    pub(crate) fn parse(mut buf: ParseBuffer) -> Result<OMAPTable> {
+        let mut records = Vec::with_capacity(buf.len() / 8);
+        while buf.len() > 0 {
+            records.push(OMAPRecord {
+                source_address: buf.parse_u32()?,
+                target_address: buf.parse_u32()?,
+            });
+        }
+
+        Ok(OMAPTable { records })
+    }
+
+    /// Returns the records of this table, in ascending order of `source_address`.
+    pub fn records(&self) -> &[OMAPRecord] {
+        &self.records
+    }
+
+    /// Maps an address from the source layout to the target layout.
+    ///
+    /// Returns `None` if the address precedes the first record, or if it lies in a range which
+    /// does not exist in the target layout.
+    pub fn lookup(&self, address: u32) -> Option<u32> {
+        // find the last record starting at or before this address
+        let index = match self.records.binary_search_by(|r| r.source_address.cmp(&address)) {
+            Ok(i) => i,
+            Err(0) => return None,
+            Err(i) => i - 1,
+        };
+
+        let record = self.records[index];
+        if record.target_address == 0 {
+            return None;
+        }
+
+        Some(record.target_address.wrapping_add(address - record.source_address))
+    }
+}
+
+/// Translates addresses between the `segment:offset` form used by symbols, RVAs in the original
+/// layout of the executable, and RVAs in the final (possibly transformed) executable.
+///
+/// For executables which were not rewritten after linking, the original and final layouts are
+/// the same and the translator simply adds the section's virtual address. Otherwise, it applies
+/// the `OmapFromSource` and `OmapToSource` tables.
+///
+/// Created by `PDB::address_translator()`.
+///
+/// # Example
+///
+/// ```
+/// # use pdb::FallibleIterator;
+/// #
+/// # fn test() -> pdb::Result<()> {
+/// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+/// let mut pdb = pdb::PDB::open(file)?;
+/// let translator = pdb.address_translator()?;
+///
+/// let symbol_table = pdb.global_symbols()?;
+/// let mut symbols = symbol_table.iter();
+/// while let Some(symbol) = symbols.next()? {
+///     if let Ok(data) = symbol.parse() {
+///         if let Some(rva) = translator.symbol_rva(&data) {
+///             println!("{:08x} {}", rva, symbol.name()?);
+///         }
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug,Clone)]
+pub struct AddressTranslator {
+    /// The sections symbols refer to, i.e. the original layout.
+    original_sections: Vec<ImageSectionHeader>,
+
+    /// Maps original RVAs to final RVAs, if the executable was transformed.
+    omap_from_source: Option<OMAPTable>,
+
+    /// Maps final RVAs to original RVAs, if the executable was transformed.
+    omap_to_source: Option<OMAPTable>,
+}
+
+impl AddressTranslator {
+    pub(crate) fn new(sections: Vec<ImageSectionHeader>,
+                      original_sections: Option<Vec<ImageSectionHeader>>,
+                      omap_from_source: Option<OMAPTable>,
+                      omap_to_source: Option<OMAPTable>) -> AddressTranslator {
+        AddressTranslator {
+            // if there are original sections, symbols refer to those rather than the final ones
+            original_sections: original_sections.unwrap_or(sections),
+            omap_from_source,
+            omap_to_source,
+        }
+    }
+
+    /// Returns true if the executable was rewritten after linking, meaning that addresses must
+    /// be mapped through OMAP tables.
+    pub fn is_transformed(&self) -> bool {
+        self.omap_from_source.is_some() || self.omap_to_source.is_some()
+    }
+
+    /// Converts an RVA in the original layout to an RVA in the final executable.
+    ///
+    /// Returns `None` if the address was removed by the transformation.
+    pub fn original_rva_to_rva(&self, original_rva: u32) -> Option<u32> {
+        match self.omap_from_source {
+            Some(ref omap) => omap.lookup(original_rva),
+            None => Some(original_rva),
+        }
+    }
+
+    /// Converts an RVA in the final executable to an RVA in the original layout.
+    ///
+    /// Returns `None` if the address does not correspond to anything in the original layout.
+    pub fn rva_to_original_rva(&self, rva: u32) -> Option<u32> {
+        match self.omap_to_source {
+            Some(ref omap) => omap.lookup(rva),
+            None => Some(rva),
+        }
+    }
+
+    /// Converts a `segment:offset` address, as found in symbols, to an RVA in the original
+    /// layout.
+    ///
+    /// Returns `None` if `segment` does not refer to a known section.
+    pub fn segment_offset_to_original_rva(&self, segment: u16, offset: u32) -> Option<u32> {
+        // segments are one-based section indexes
+        if segment == 0 {
+            return None;
+        }
+
+        self.original_sections.get(segment as usize - 1)
+            .map(|section| section.virtual_address.wrapping_add(offset))
+    }
+
+    /// Converts a `segment:offset` address, as found in symbols, to an RVA in the final
+    /// executable.
+    ///
+    /// Returns `None` if `segment` does not refer to a known section, or if the address was
+    /// removed by the transformation.
+    pub fn segment_offset_to_rva(&self, segment: u16, offset: u32) -> Option<u32> {
+        self.segment_offset_to_original_rva(segment, offset)
+            .and_then(|rva| self.original_rva_to_rva(rva))
+    }
+
+    /// Converts an RVA in the final executable to the `segment:offset` form used by symbols.
+    ///
+    /// Returns `None` if the address does not correspond to anything in the original layout, or
+    /// if it lies outside of every section.
+    pub fn rva_to_segment_offset(&self, rva: u32) -> Option<(u16, u32)> {
+        let original_rva = self.rva_to_original_rva(rva)?;
+
+        self.original_sections.iter()
+            .position(|section| {
+                // sections with uninitialized data can have a virtual size of zero
+                let size = cmp::max(section.virtual_size, section.size_of_raw_data);
+                original_rva >= section.virtual_address &&
+                    original_rva - section.virtual_address < size
+            })
+            .map(|index| {
+                let section = &self.original_sections[index];
+                ((index + 1) as u16, original_rva - section.virtual_address)
+            })
+    }
+
+    /// Returns the RVA of a symbol in the final executable, if it has an address.
+    pub fn symbol_rva(&self, symbol: &SymbolData) -> Option<u32> {
+        symbol.segment_offset()
+            .and_then(|(segment, offset)| self.segment_offset_to_rva(segment, offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod address_translator {
+        use omap::*;
+        use pe::ImageSectionHeader;
+
+        fn section(virtual_address: u32, virtual_size: u32) -> ImageSectionHeader {
+            ImageSectionHeader {
+                name: [0; 8],
+                virtual_size,
+                virtual_address,
+                size_of_raw_data: 0,
+                pointer_to_raw_data: 0,
+                pointer_to_relocations: 0,
+                pointer_to_line_numbers: 0,
+                number_of_relocations: 0,
+                number_of_line_numbers: 0,
+                characteristics: 0,
+            }
+        }
+
+        fn omap(records: &[(u32, u32)]) -> OMAPTable {
+            let mut data = Vec::new();
+            for &(source, target) in records {
+                for value in &[source, target] {
+                    data.extend_from_slice(&[*value as u8, (*value >> 8) as u8,
+                                             (*value >> 16) as u8, (*value >> 24) as u8]);
+                }
+            }
+            OMAPTable::parse(ParseBuffer::from(data.as_slice())).expect("parse")
+        }
+
+        #[test]
+        fn test_omap_lookup() {
+            let table = omap(&[(0x1000, 0x5000), (0x1100, 0), (0x1200, 0x1000)]);
+            assert_eq!(table.records().len(), 3);
+
+            assert_eq!(table.lookup(0xfff), None);
+            assert_eq!(table.lookup(0x1000), Some(0x5000));
+            assert_eq!(table.lookup(0x10ff), Some(0x50ff));
+            assert_eq!(table.lookup(0x1100), None);
+            assert_eq!(table.lookup(0x11ff), None);
+            assert_eq!(table.lookup(0x1234), Some(0x1034));
+        }
+
+        #[test]
+        fn test_omap_truncated() {
+            let data = [0u8; 12];
+            match OMAPTable::parse(ParseBuffer::from(&data[..])) {
+                Err(Error::UnexpectedEof) => (),
+                _ => panic!("expected UnexpectedEof"),
+            }
+        }
+
+        #[test]
+        fn test_untransformed() {
+            let translator = AddressTranslator::new(
+                vec![section(0x1000, 0x800), section(0x2000, 0x100)], None, None, None);
+
+            assert!(!translator.is_transformed());
+            assert_eq!(translator.segment_offset_to_rva(1, 0x10), Some(0x1010));
+            assert_eq!(translator.segment_offset_to_rva(2, 0x10), Some(0x2010));
+            assert_eq!(translator.segment_offset_to_rva(0, 0x10), None);
+            assert_eq!(translator.segment_offset_to_rva(3, 0x10), None);
+
+            assert_eq!(translator.rva_to_segment_offset(0x1010), Some((1, 0x10)));
+            assert_eq!(translator.rva_to_segment_offset(0x20ff), Some((2, 0xff)));
+            assert_eq!(translator.rva_to_segment_offset(0x1900), None);
+        }
+
+        #[test]
+        fn test_transformed() {
+            // the optimizer moved original 0x1000..0x1100 to 0x3000, dropped 0x1100..0x1200, and
+            // moved 0x1200.. to 0x1000
+            let translator = AddressTranslator::new(
+                vec![section(0x1000, 0x3000)],
+                Some(vec![section(0x1000, 0x400)]),
+                Some(omap(&[(0x1000, 0x3000), (0x1100, 0), (0x1200, 0x1000)])),
+                Some(omap(&[(0x1000, 0x1200), (0x1200, 0), (0x3000, 0x1000), (0x3100, 0)])));
+
+            assert!(translator.is_transformed());
+            assert_eq!(translator.segment_offset_to_original_rva(1, 0x20), Some(0x1020));
+            assert_eq!(translator.segment_offset_to_rva(1, 0x20), Some(0x3020));
+            assert_eq!(translator.segment_offset_to_rva(1, 0x120), None);
+            assert_eq!(translator.segment_offset_to_rva(1, 0x220), Some(0x1020));
+
+            assert_eq!(translator.rva_to_original_rva(0x3020), Some(0x1020));
+            assert_eq!(translator.rva_to_segment_offset(0x3020), Some((1, 0x20)));
+            assert_eq!(translator.rva_to_segment_offset(0x1020), Some((1, 0x220)));
+            assert_eq!(translator.rva_to_segment_offset(0x2000), None);
+        }
+    }
+}
@@ -6,20 +6,29 @@
 // copied, modified, or distributed except according to those terms.
 
 use dbi;
+use frame;
 use module_info;
 use msf;
+use omap::{AddressTranslator, OMAPTable};
+use pe;
 use symbol;
 use tpi;
 use pdbi;
+use strings;
 
 use common::*;
-use dbi::{DebugInformation, Module};
-use module_info::ModuleInfo;
+use dbi::{DBIExtraStreams, DebugInformation, ExtraStreamKind, Module};
+use frame::{FPOTable, FrameTable};
+use line_index::{LineIndex, LineIndexBuilder};
+use module_info::{CrossModuleRef, ModuleInfo};
 use source::Source;
 use msf::{MSF, Stream};
+use pe::ImageSectionHeader;
 use symbol::SymbolTable;
 use tpi::TypeInformation;
 use pdbi::PDBInformation;
+use strings::StringTable;
+use FallibleIterator;
 
 /// Some streams have a fixed stream index.
 /// http://llvm.org/docs/PDB/index.html
@@ -42,6 +51,9 @@ pub struct PDB<'s, S> {
 
     /// Memoize the `dbi::Header`, since it contains stream numbers we sometimes need
     dbi_header: Option<dbi::Header>,
+
+    /// Memoize the `DBIExtraStreams`, since they locate streams like the section headers
+    dbi_extra_streams: Option<DBIExtraStreams>,
 }
 
 impl<'s, S: Source<'s> + 's> PDB<'s, S> {
@@ -51,9 +63,11 @@ impl<'s, S: Source<'s> + 's> PDB<'s, S> {
     /// involves reading the header, a block near the end of the file, and finally the stream table
     /// itself. It does not access or validate any of the contents of the rest of the PDB.
     ///
+    /// Both the current MSF 7.00 container format and the older PDB 2.00 format produced by
+    /// Visual C++ 6.0 and earlier are supported.
+    ///
     /// # Errors
     ///
-    /// * `Error::UnimplementedFeature` if the PDB file predates ~2002
     /// * `Error::UnrecognizedFileFormat` if the `Source` does not appear to be a PDB file
     /// * `Error::IoError` if returned by the `Source`
     /// * `Error::PageReferenceOutOfRange`, `Error::InvalidPageSize` if the PDB file seems corrupt
@@ -63,6 +77,7 @@ impl<'s, S: Source<'s> + 's> PDB<'s, S> {
         Ok(PDB{
             msf: msf,
             dbi_header: None,
+            dbi_extra_streams: None,
         })
     }
 
@@ -151,6 +166,192 @@ impl<'s, S: Source<'s> + 's> PDB<'s, S> {
         Ok(header)
     }
 
+    fn dbi_extra_streams(&mut self) -> Result<DBIExtraStreams> {
+        if let Some(extra) = self.dbi_extra_streams {
+            return Ok(extra);
+        }
+
+        // the debug header is at the very end of the DBI stream, so read the whole thing
+        let extra = self.debug_information()?.extra_streams()?;
+        self.dbi_extra_streams = Some(extra);
+
+        Ok(extra)
+    }
+
+    /// Retrieve one of the optional debug streams described by the debug information stream.
+    ///
+    /// These streams contain data the linker copied out of the executable, such as its section
+    /// headers, FPO records, and OMAP tables. Returns `Ok(None)` if the PDB does not contain the
+    /// requested kind of stream.
+    ///
+    /// The stream numbers are read from the debug information stream the first time this is
+    /// called and remembered afterwards.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::StreamNotFound` if the PDB does not contain a debug information stream, or if
+    ///   the debug information stream refers to a stream that does not exist
+    /// * `Error::IoError` if returned by the `Source`
+    /// * `Error::PageReferenceOutOfRange` if the PDB file seems corrupt
+    /// * `Error::UnimplementedFeature` if the debug information header predates ~1995
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn test() -> pdb::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb::PDB::open(file)?;
+    /// if let Some(stream) = pdb.extra_stream(pdb::ExtraStreamKind::SectionHeaders)? {
+    ///     println!("section headers take {} bytes", stream.parse_buffer().len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn extra_stream(&mut self, kind: ExtraStreamKind) -> Result<Option<Stream<'s>>> {
+        match self.dbi_extra_streams()?.stream(kind) {
+            Some(stream) => Ok(Some(self.msf.get(stream as u32, None)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Retrieve the executable's section headers, as copied into the PDB by the linker.
+    ///
+    /// Symbols refer to addresses as `segment:offset`, where `segment` is a one-based index into
+    /// these headers. Returns `Ok(None)` if the PDB does not contain section headers.
+    ///
+    /// If the executable was rewritten by a post-link optimizer, these describe the final image;
+    /// see `original_sections()`.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::StreamNotFound` if the PDB does not contain a debug information stream
+    /// * `Error::IoError` if returned by the `Source`
+    /// * `Error::PageReferenceOutOfRange` if the PDB file seems corrupt
+    /// * `Error::UnexpectedEof` if the section headers stream is truncated
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn test() -> pdb::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb::PDB::open(file)?;
+    /// if let Some(sections) = pdb.sections()? {
+    ///     for section in &sections {
+    ///         println!("{} at RVA {:#x}", section.name(), section.virtual_address);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sections(&mut self) -> Result<Option<Vec<ImageSectionHeader>>> {
+        self.section_headers(ExtraStreamKind::SectionHeaders)
+    }
+
+    /// Retrieve the executable's section headers as they were before the executable was
+    /// rewritten by a post-link optimizer such as BBT.
+    ///
+    /// Returns `Ok(None)` if the executable was not rewritten, in which case `sections()` describes
+    /// the only layout.
+    ///
+    /// # Errors
+    ///
+    /// Fails under the same conditions as `sections()`.
+    pub fn original_sections(&mut self) -> Result<Option<Vec<ImageSectionHeader>>> {
+        self.section_headers(ExtraStreamKind::OriginalSectionHeaders)
+    }
+
+    fn section_headers(&mut self, kind: ExtraStreamKind) -> Result<Option<Vec<ImageSectionHeader>>> {
+        match self.extra_stream(kind)? {
+            Some(stream) => Ok(Some(pe::parse_section_headers(stream.parse_buffer())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Build an `AddressTranslator` for converting between symbol addresses and RVAs.
+    ///
+    /// This reads the section headers and, if the executable was rewritten after linking, the
+    /// original section headers and both OMAP tables. If the PDB contains no section headers at
+    /// all, the resulting translator cannot translate any `segment:offset` addresses.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::StreamNotFound` if the PDB does not contain a debug information stream
+    /// * `Error::IoError` if returned by the `Source`
+    /// * `Error::PageReferenceOutOfRange` if the PDB file seems corrupt
+    /// * `Error::UnexpectedEof` if the section headers or OMAP streams are truncated
+    pub fn address_translator(&mut self) -> Result<AddressTranslator> {
+        let sections = self.sections()?.unwrap_or_default();
+        let original_sections = self.original_sections()?;
+        let omap_from_source = self.omap_table(ExtraStreamKind::OmapFromSource)?;
+        let omap_to_source = self.omap_table(ExtraStreamKind::OmapToSource)?;
+
+        Ok(AddressTranslator::new(sections, original_sections, omap_from_source, omap_to_source))
+    }
+
+    fn omap_table(&mut self, kind: ExtraStreamKind) -> Result<Option<OMAPTable>> {
+        match self.extra_stream(kind)? {
+            Some(stream) => Ok(Some(OMAPTable::parse(stream.parse_buffer())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Retrieve the table of classic `FPO_DATA` records describing x86 stack frames.
+    ///
+    /// Returns `Ok(None)` if the PDB does not contain FPO data, which is typical for anything other
+    /// than 32-bit x86 code.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::StreamNotFound` if the PDB does not contain a debug information stream
+    /// * `Error::IoError` if returned by the `Source`
+    /// * `Error::PageReferenceOutOfRange` if the PDB file seems corrupt
+    pub fn fpo_table(&mut self) -> Result<Option<FPOTable<'s>>> {
+        Ok(self.extra_stream(ExtraStreamKind::Fpo)?.map(frame::new_fpo_table))
+    }
+
+    /// Retrieve the table of "new FPO" `FRAMEDATA` records describing x86 stack frames.
+    ///
+    /// The frame programs these records refer to are stored in the PDB's `/names` string table,
+    /// which is loaded along with the records. Returns `Ok(None)` if the PDB does not contain
+    /// frame data.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::StreamNotFound` if the PDB does not contain a debug information stream
+    /// * `Error::StreamNameNotFound` if the PDB does not contain a `/names` stream
+    /// * `Error::InvalidStringTable` if the string table header was not understood
+    /// * `Error::IoError` if returned by the `Source`
+    /// * `Error::PageReferenceOutOfRange` if the PDB file seems corrupt
+    pub fn frame_table(&mut self) -> Result<Option<FrameTable<'s>>> {
+        let stream = match self.extra_stream(ExtraStreamKind::FrameData)? {
+            Some(stream) => stream,
+            None => return Ok(None),
+        };
+
+        let strings = self.string_table()?;
+        Ok(Some(frame::new_frame_table(stream, strings)))
+    }
+
+    /// Retrieve the global string table of this PDB, stored in the `/names` stream.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::StreamNameNotFound` if the PDB does not contain a `/names` stream
+    /// * `Error::InvalidStringTable` if the string table header was not understood
+    /// * `Error::IoError` if returned by the `Source`
+    /// * `Error::PageReferenceOutOfRange` if the PDB file seems corrupt
+    pub fn string_table(&mut self) -> Result<StringTable<'s>> {
+        let stream_id = {
+            let info = self.pdb_information()?;
+            let names = info.stream_names()?;
+            let found = names.iter().find(|n| n.name.as_bytes() == b"/names").map(|n| n.stream_id);
+            found.ok_or(Error::StreamNameNotFound)?
+        };
+
+        let stream = self.msf.get(stream_id, None)?;
+        strings::new_string_table(stream)
+    }
+
     /// Retrieve the global symbol table for this PDB.
     ///
     /// The `SymbolTable` object owns a `SourceView` for the symbol records stream. This is usually
@@ -227,6 +428,99 @@ impl<'s, S: Source<'s> + 's> PDB<'s, S> {
         res
     }
 
+    /// Finds the module which exports the item a cross-module reference of `info` refers to.
+    ///
+    /// `modules` are the modules of the PDB, as returned by `DebugInformation::modules()` or
+    /// `ContributionIndex::modules()`. Returns the target module along with the item index local
+    /// to it, which the target's `ModuleInfo::global_index()` maps to a global item index.
+    ///
+    /// Returns `Ok(None)` if `info` does not import the referenced item, or if no module in
+    /// `modules` has the name recorded for the import. Module names are compared ignoring ASCII
+    /// case, since they are usually Windows paths.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::StreamNameNotFound` if the PDB does not contain a string table
+    /// * `Error::IoError` if returned by the `Source`
+    /// * `Error::PageReferenceOutOfRange` if the PDB file seems corrupt
+    /// * `Error::UnexpectedEof` if the imports or the string table are truncated
+    pub fn cross_module_target<'a, 'm>(&mut self, info: &ModuleInfo, reference: CrossModuleRef,
+                                       modules: &'a [Module<'m>]) -> Result<Option<(&'a Module<'m>, ItemIndex)>> {
+        let target = match info.resolve_cross_module_ref(reference)? {
+            Some(target) => target,
+            None => return Ok(None),
+        };
+
+        let strings = self.string_table()?;
+        let name = strings.get(target.module_name)?;
+        let module = modules.iter()
+            .find(|module| module.module_name().as_bytes().eq_ignore_ascii_case(name.as_bytes()));
+
+        Ok(module.map(|module| (module, target.local_index)))
+    }
+
+    /// Build a `LineIndex` over the line information of every module in this PDB.
+    ///
+    /// This reads every module info stream, so it is best done once and kept around. Lines whose
+    /// code cannot be mapped to an RVA, for example because it was removed by a post-link
+    /// optimizer, are left out.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::StreamNotFound` if the PDB does not contain a debug information stream
+    /// * `Error::StreamNameNotFound` if the PDB contains C13 line information but no string table
+    /// * `Error::IoError` if returned by the `Source`
+    /// * `Error::PageReferenceOutOfRange` if the PDB file seems corrupt
+    /// * `Error::UnexpectedEof` if line information is truncated
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn test() -> pdb::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb::PDB::open(file)?;
+    /// let index = pdb.line_index()?;
+    /// let file = index.find_file("c:\\users\\user\\desktop\\self\\foo.cpp".into());
+    /// if let Some(file) = file {
+    ///     for line in index.find_source(file, 29) {
+    ///         println!("{:08x}+{:x}", line.rva, line.length);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # test().expect("test");
+    /// ```
+    pub fn line_index(&mut self) -> Result<LineIndex> {
+        let translator = self.address_translator()?;
+        let strings = match self.string_table() {
+            Ok(strings) => Some(strings),
+            // only C13 line information refers to the string table
+            Err(Error::StreamNameNotFound) => None,
+            Err(e) => return Err(e),
+        };
+
+        let dbi = self.debug_information()?;
+        let mut modules = dbi.modules()?;
+        let mut builder = LineIndexBuilder::new();
+        while let Some(module) = modules.next()? {
+            // modules without a module info stream have no lines
+            if !module.has_module_info() {
+                continue;
+            }
+
+            let info = self.module_info(&module)?;
+            let mut lines = info.lines()?;
+            while let Some(line) = lines.next()? {
+                if let Some(rva) = translator.segment_offset_to_rva(line.segment, line.offset) {
+                    let name = info.resolve_file_name(line.file_index, strings.as_ref())?;
+                    builder.add(rva, &line, name);
+                }
+            }
+        }
+
+        Ok(builder.build())
+    }
+
     /// Retrieve a stream by its index to read its contents as bytes.
     ///
     /// # Errors
@@ -0,0 +1,151 @@
+// Copyright 2017 pdb Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Definitions for PE executable structures which the linker copies into the PDB.
+
+use common::*;
+
+/// A PE `IMAGE_SECTION_HEADER`, as copied into the PDB by the linker.
+///
+/// Symbols refer to code and data by `segment:offset`, where `segment` is the one-based index of
+/// a section in the executable. The section's `virtual_address` turns such an address into an
+/// RVA.
+///
+/// See `winnt.h` or the PE format documentation:
+/// https://docs.microsoft.com/en-us/windows/desktop/Debug/pe-format#section-table-section-headers
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub struct ImageSectionHeader {
+    /// The section name, padded with NUL bytes. See `name()`.
+    pub name: [u8; 8],
+
+    /// The size of the section when loaded into memory. Named `Misc.VirtualSize` in `winnt.h`.
+    pub virtual_size: u32,
+
+    /// The RVA of the section's first byte when loaded into memory.
+    pub virtual_address: u32,
+
+    /// The size of the section's initialized data on disk.
+    pub size_of_raw_data: u32,
+
+    /// The file offset of the section's data.
+    pub pointer_to_raw_data: u32,
+
+    /// The file offset of the section's relocations.
+    pub pointer_to_relocations: u32,
+
+    /// The file offset of the section's COFF line numbers.
+    pub pointer_to_line_numbers: u32,
+
+    /// The number of relocations for the section.
+    pub number_of_relocations: u16,
+
+    /// The number of COFF line numbers for the section.
+    pub number_of_line_numbers: u16,
+
+    /// The `IMAGE_SCN_*` flags describing the section.
+    pub characteristics: u32,
+}
+
+impl ImageSectionHeader {
+    /// The size of an `IMAGE_SECTION_HEADER` on disk.
+    pub const SIZE: usize = 40;
+
+    fn parse(buf: &mut ParseBuffer) -> Result<Self> {
+        let mut name = [0u8; 8];
+        name.copy_from_slice(buf.take(8)?);
+
+        Ok(ImageSectionHeader {
+            name,
+            virtual_size: buf.parse_u32()?,
+            virtual_address: buf.parse_u32()?,
+            size_of_raw_data: buf.parse_u32()?,
+            pointer_to_raw_data: buf.parse_u32()?,
+            pointer_to_relocations: buf.parse_u32()?,
+            pointer_to_line_numbers: buf.parse_u32()?,
+            number_of_relocations: buf.parse_u16()?,
+            number_of_line_numbers: buf.parse_u16()?,
+            characteristics: buf.parse_u32()?,
+        })
+    }
+
+    /// Returns the section name, without any trailing NUL padding.
+    ///
+    /// Names of exactly eight bytes are not NUL-terminated.
+    pub fn name(&self) -> RawString<'_> {
+        let len = self.name.iter().position(|ch| *ch == 0).unwrap_or(self.name.len());
+        RawString::from(&self.name[..len])
+    }
+
+    /// Returns true if `rva` falls within this section when loaded into memory.
+    pub fn contains_rva(&self, rva: u32) -> bool {
+        rva >= self.virtual_address && rva - self.virtual_address < self.virtual_size
+    }
+}
+
+/// Parses a stream of consecutive `IMAGE_SECTION_HEADER`s.
+pub fn parse_section_headers(mut buf: ParseBuffer) -> Result<Vec<ImageSectionHeader>> {
+    let mut headers = Vec::with_capacity(buf.len() / ImageSectionHeader::SIZE);
+    while buf.len() > 0 {
+        headers.push(ImageSectionHeader::parse(&mut buf)?);
+    }
+    Ok(headers)
+}
+
+#[cfg(test)]
+mod tests {
+    mod section_header {
+        use pe::*;
+
+        #[test]
+        fn test_parse() {
+            let data = [
+                0x2e, 0x74, 0x65, 0x78, 0x74, 0x00, 0x00, 0x00, // ".text"
+                0x34, 0x12, 0x00, 0x00, // virtual_size
+                0x00, 0x10, 0x00, 0x00, // virtual_address
+                0x00, 0x14, 0x00, 0x00, // size_of_raw_data
+                0x00, 0x04, 0x00, 0x00, // pointer_to_raw_data
+                0x00, 0x00, 0x00, 0x00, // pointer_to_relocations
+                0x00, 0x00, 0x00, 0x00, // pointer_to_line_numbers
+                0x00, 0x00, 0x00, 0x00, // number_of_relocations, number_of_line_numbers
+                0x20, 0x00, 0x00, 0x60, // characteristics
+            ];
+
+            let headers = parse_section_headers(ParseBuffer::from(&data[..])).expect("parse");
+            assert_eq!(headers.len(), 1);
+
+            let text = &headers[0];
+            assert_eq!(text.name().as_bytes(), b".text");
+            assert_eq!(text.virtual_size, 0x1234);
+            assert_eq!(text.virtual_address, 0x1000);
+            assert_eq!(text.size_of_raw_data, 0x1400);
+            assert_eq!(text.pointer_to_raw_data, 0x400);
+            assert_eq!(text.characteristics, 0x6000_0020);
+
+            assert!(!text.contains_rva(0xfff));
+            assert!(text.contains_rva(0x1000));
+            assert!(text.contains_rva(0x2233));
+            assert!(!text.contains_rva(0x2234));
+        }
+
+        #[test]
+        fn test_full_name() {
+            let mut data = [0u8; 40];
+            data[..8].copy_from_slice(b".textbss");
+            let headers = parse_section_headers(ParseBuffer::from(&data[..])).expect("parse");
+            assert_eq!(headers[0].name().as_bytes(), b".textbss");
+        }
+
+        #[test]
+        fn test_truncated() {
+            let data = [0u8; 50];
+            match parse_section_headers(ParseBuffer::from(&data[..])) {
+                Err(Error::UnexpectedEof) => (),
+                _ => panic!("expected UnexpectedEof"),
+            }
+        }
+    }
+}
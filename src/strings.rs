@@ -0,0 +1,240 @@
+// Copyright 2017 pdb Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use common::*;
+use msf::*;
+
+/// The signature at the start of the `/names` stream.
+const STRING_TABLE_SIGNATURE: u32 = 0xeffe_effe;
+
+/// The size of the `/names` stream header: signature, hash version, and string buffer size.
+const STRING_TABLE_HEADER_SIZE: usize = 12;
+
+/// The hash function used to place strings into the buckets of a `StringTable`.
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub enum StringTableHashVersion {
+    /// `LHashPbCb`, the hash used throughout the PDB format.
+    V1,
+    /// `LHashPbCbV2`, used by newer linkers.
+    V2,
+}
+
+/// The global string table of a PDB, stored in the stream named `/names`.
+///
+/// Other structures refer to strings by their byte offset into this table. This includes file
+/// names in C13 line information, FRAMEDATA program strings, and various symbols. The table also
+/// contains a hash table which maps strings back to their offsets; see `find()`.
+///
+/// The format is described by `NMT` in the Microsoft PDB source:
+/// https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/PDB/include/nmt.h
+#[derive(Debug)]
+pub struct StringTable<'s> {
+    stream: Stream<'s>,
+    hash_version: StringTableHashVersion,
+    names_size: usize,
+    bucket_count: usize,
+    name_count: usize,
+}
+
+pub fn new_string_table(stream: Stream) -> Result<StringTable> {
+    let hash_version;
+    let names_size;
+    let bucket_count;
+    let name_count;
+
+    {
+        let mut buf = stream.parse_buffer();
+        if buf.parse_u32()? != STRING_TABLE_SIGNATURE {
+            return Err(Error::InvalidStringTable("bad signature"));
+        }
+
+        hash_version = match buf.parse_u32()? {
+            1 => StringTableHashVersion::V1,
+            2 => StringTableHashVersion::V2,
+            _ => return Err(Error::InvalidStringTable("unknown hash version")),
+        };
+
+        names_size = buf.parse_u32()? as usize;
+        buf.take(names_size)?;
+
+        // the hash buckets follow the string data
+        bucket_count = buf.parse_u32()? as usize;
+        buf.take(bucket_count * 4)?;
+        name_count = buf.parse_u32()? as usize;
+    }
+
+    Ok(StringTable {
+        stream,
+        hash_version,
+        names_size,
+        bucket_count,
+        name_count,
+    })
+}
+
+impl<'s> StringTable<'s> {
+    /// Returns the hash function used by this table.
+    pub fn hash_version(&self) -> StringTableHashVersion {
+        self.hash_version
+    }
+
+    /// Returns the number of strings in this table, as recorded in its hash table.
+    pub fn len(&self) -> usize {
+        self.name_count
+    }
+
+    /// Returns true if this table contains no strings.
+    pub fn is_empty(&self) -> bool {
+        self.name_count == 0
+    }
+
+    /// Returns the NUL-terminated string starting at `offset` bytes into the table.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::UnexpectedEof` if `offset` lies outside of the table, or if the string is not
+    ///   terminated within the table
+    pub fn get(&self, offset: u32) -> Result<RawString<'_>> {
+        let names = self.names()?;
+        let offset = offset as usize;
+        if offset >= names.len() {
+            return Err(Error::UnexpectedEof);
+        }
+
+        ParseBuffer::from(&names[offset..]).parse_cstring()
+    }
+
+    /// Finds the offset of `name` in this table using its hash buckets.
+    ///
+    /// Returns `Ok(None)` if the table does not contain `name`.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::UnexpectedEof` if a bucket refers to a string outside of the table
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn test() -> pdb::Result<()> {
+    /// let file = std::fs::File::open("fixtures/self/foo.pdb")?;
+    /// let mut pdb = pdb::PDB::open(file)?;
+    /// let strings = pdb.string_table()?;
+    /// if let Some(offset) = strings.find("c:\\users\\user\\desktop\\self\\foo.cpp".into())? {
+    ///     assert_eq!(strings.get(offset)?, "c:\\users\\user\\desktop\\self\\foo.cpp".into());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # test().expect("test");
+    /// ```
+    pub fn find(&self, name: RawString) -> Result<Option<u32>> {
+        let buckets = self.buckets()?;
+        if self.bucket_count == 0 {
+            return Ok(None);
+        }
+
+        let hash = match self.hash_version {
+            StringTableHashVersion::V1 => hash_v1(name.as_bytes()),
+            StringTableHashVersion::V2 => hash_v2(name.as_bytes()),
+        };
+
+        // the buckets are an open-addressed hash table with linear probing
+        let start = hash as usize % self.bucket_count;
+        for i in 0..self.bucket_count {
+            let index = (start + i) % self.bucket_count;
+            let offset = ParseBuffer::from(&buckets[index * 4..]).parse_u32()?;
+            if offset == 0 {
+                // an empty bucket ends the probe sequence
+                break;
+            }
+
+            if self.get(offset)? == name {
+                return Ok(Some(offset));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn names(&self) -> Result<&[u8]> {
+        let mut buf = self.stream.parse_buffer();
+        buf.take(STRING_TABLE_HEADER_SIZE)?;
+        buf.take(self.names_size)
+    }
+
+    fn buckets(&self) -> Result<&[u8]> {
+        let mut buf = self.stream.parse_buffer();
+        buf.take(STRING_TABLE_HEADER_SIZE + self.names_size + 4)?;
+        buf.take(self.bucket_count * 4)
+    }
+}
+
+/// The original PDB string hash, `LHashPbCb` in the Microsoft PDB source:
+/// https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/PDB/include/misc.h
+fn hash_v1(bytes: &[u8]) -> u32 {
+    let mut result: u32 = 0;
+
+    let mut chunks = bytes.chunks(4);
+    for chunk in &mut chunks {
+        result ^= match chunk.len() {
+            4 => u32::from(chunk[0]) | u32::from(chunk[1]) << 8 |
+                 u32::from(chunk[2]) << 16 | u32::from(chunk[3]) << 24,
+            // hash a trailing 2-byte word, then a trailing byte
+            3 => (u32::from(chunk[0]) | u32::from(chunk[1]) << 8) ^ u32::from(chunk[2]),
+            2 => u32::from(chunk[0]) | u32::from(chunk[1]) << 8,
+            _ => u32::from(chunk[0]),
+        };
+    }
+
+    result |= 0x2020_2020;
+    result ^= result >> 11;
+    result ^ (result >> 16)
+}
+
+/// The newer PDB string hash, `LHashPbCbV2` in the Microsoft PDB source:
+/// https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/PDB/include/misc.h
+fn hash_v2(bytes: &[u8]) -> u32 {
+    let mut hash: u32 = 0xb170_a1bf;
+    let mut mix = |value: u32| {
+        hash = hash.wrapping_add(value);
+        hash = hash.wrapping_add(hash << 10);
+        hash ^= hash >> 6;
+    };
+
+    let words = bytes.len() / 4;
+    for word in bytes[..words * 4].chunks(4) {
+        mix(u32::from(word[0]) | u32::from(word[1]) << 8 |
+            u32::from(word[2]) << 16 | u32::from(word[3]) << 24);
+    }
+    for byte in &bytes[words * 4..] {
+        mix(u32::from(*byte));
+    }
+
+    hash.wrapping_mul(1_664_525).wrapping_add(1_013_904_223)
+}
+
+#[cfg(test)]
+mod tests {
+    mod hash {
+        use strings::*;
+
+        #[test]
+        fn test_hash_v1() {
+            // 0x61, folded with 0x20202020, then mixed by the two shifts
+            assert_eq!(hash_v1(b"a"), 0x2024_0441);
+
+            // setting 0x20 in every byte makes the hash case-insensitive for ASCII letters
+            assert_eq!(hash_v1(b"FOO.CPP"), hash_v1(b"foo.cpp"));
+            assert_ne!(hash_v1(b"foo.cpp"), hash_v1(b"foo.hpp"));
+        }
+
+        #[test]
+        fn test_hash_v2() {
+            assert_eq!(hash_v2(b""), 0xb170_a1bfu32.wrapping_mul(1_664_525).wrapping_add(1_013_904_223));
+            assert_ne!(hash_v2(b"FOO.CPP"), hash_v2(b"foo.cpp"));
+        }
+    }
+}
@@ -0,0 +1,367 @@
+// Copyright 2017 pdb Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+// Binary annotations are described by `BinaryAnnotationOpcode` in cvinfo.h:
+//   https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L4529
+// and decoded by `CVUncompressData`:
+//   https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvinfo.h#L4633
+
+use std::result;
+
+use common::*;
+use FallibleIterator;
+use module_info::{InlineeLine, LineInfo, LineInfoKind};
+use super::ProcedureSymbol;
+
+/// An instruction in the binary annotations of an `S_INLINESITE` symbol.
+///
+/// Together, the annotations of an inline site map the ranges of code it covers to lines in the
+/// inlined function. Use `InlineSiteSymbol::lines()` to evaluate them.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub enum BinaryAnnotation {
+    /// Sets the code offset.
+    CodeOffset(u32),
+    /// Sets the separated code chunk the following offsets refer to, where `0` is the main chunk.
+    ChangeCodeOffsetBase(u32),
+    /// Advances the code offset, and starts a new range of code.
+    ChangeCodeOffset(u32),
+    /// Sets the length of the current range of code, and advances the code offset past it.
+    ChangeCodeLength(u32),
+    /// Sets the source file, as a `FileIndex` of the module.
+    ChangeFile(FileIndex),
+    /// Moves the current line.
+    ChangeLineOffset(i32),
+    /// Sets the number of lines spanned by the current line.
+    ChangeLineEndDelta(u32),
+    /// Sets whether lines are expressions (`0`) or statements (`1`).
+    ChangeRangeKind(u32),
+    /// Sets the start column.
+    ChangeColumnStart(u32),
+    /// Moves the end column.
+    ChangeColumnEndDelta(i32),
+    /// Advances the code offset and moves the current line, and starts a new range of code.
+    ChangeCodeOffsetAndLineOffset(u32, i32),
+    /// Sets the length of the next range of code, advances the code offset, and starts the range.
+    ChangeCodeLengthAndCodeOffset(u32, u32),
+    /// Sets the end column.
+    ChangeColumnEnd(u32),
+}
+
+impl BinaryAnnotation {
+    /// Returns true if this annotation starts a new range of code.
+    fn starts_range(&self) -> bool {
+        matches!(*self,
+            BinaryAnnotation::ChangeCodeOffset(_) |
+            BinaryAnnotation::ChangeCodeOffsetAndLineOffset(_, _) |
+            BinaryAnnotation::ChangeCodeLengthAndCodeOffset(_, _))
+    }
+}
+
+/// Reads an unsigned integer compressed with `CVCompressData`.
+fn parse_compressed(buf: &mut ParseBuffer) -> Result<u32> {
+    let first = u32::from(buf.parse_u8()?);
+    if first & 0x80 == 0x00 {
+        return Ok(first);
+    }
+
+    if first & 0xc0 == 0x80 {
+        let second = u32::from(buf.parse_u8()?);
+        return Ok((first & 0x3f) << 8 | second);
+    }
+
+    if first & 0xe0 == 0xc0 {
+        let mut value = first & 0x1f;
+        for _ in 0..3 {
+            value = value << 8 | u32::from(buf.parse_u8()?);
+        }
+        return Ok(value);
+    }
+
+    Err(Error::InvalidBinaryAnnotation("invalid compressed integer"))
+}
+
+/// Decodes a signed integer stored by `EncodeSignedInt32`, with the sign in the lowest bit.
+fn decode_signed(value: u32) -> i32 {
+    if value & 1 != 0 {
+        -((value >> 1) as i32)
+    } else {
+        (value >> 1) as i32
+    }
+}
+
+/// The binary annotations at the end of an `S_INLINESITE` symbol, borrowed from the symbol
+/// record.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct BinaryAnnotations<'t> {
+    data: &'t [u8],
+}
+
+impl<'t> BinaryAnnotations<'t> {
+    pub(crate) fn new(data: &'t [u8]) -> Self {
+        BinaryAnnotations { data }
+    }
+
+    /// Returns an iterator which decodes the annotations.
+    pub fn iter(&self) -> BinaryAnnotationIter<'t> {
+        BinaryAnnotationIter { buf: ParseBuffer::from(self.data) }
+    }
+}
+
+/// An iterator over the `BinaryAnnotation`s of an inline site.
+#[derive(Debug)]
+pub struct BinaryAnnotationIter<'t> {
+    buf: ParseBuffer<'t>,
+}
+
+impl<'t> FallibleIterator for BinaryAnnotationIter<'t> {
+    type Item = BinaryAnnotation;
+    type Error = Error;
+
+    fn next(&mut self) -> result::Result<Option<Self::Item>, Self::Error> {
+        if self.buf.len() == 0 {
+            return Ok(None);
+        }
+
+        let buf = &mut self.buf;
+        let annotation = match parse_compressed(buf)? {
+            // the annotations are padded to a multiple of four bytes with zeros
+            0 => {
+                *buf = ParseBuffer::from(&[][..]);
+                return Ok(None);
+            }
+            1 => BinaryAnnotation::CodeOffset(parse_compressed(buf)?),
+            2 => BinaryAnnotation::ChangeCodeOffsetBase(parse_compressed(buf)?),
+            3 => BinaryAnnotation::ChangeCodeOffset(parse_compressed(buf)?),
+            4 => BinaryAnnotation::ChangeCodeLength(parse_compressed(buf)?),
+            5 => BinaryAnnotation::ChangeFile(parse_compressed(buf)?),
+            6 => BinaryAnnotation::ChangeLineOffset(decode_signed(parse_compressed(buf)?)),
+            7 => BinaryAnnotation::ChangeLineEndDelta(parse_compressed(buf)?),
+            8 => BinaryAnnotation::ChangeRangeKind(parse_compressed(buf)?),
+            9 => BinaryAnnotation::ChangeColumnStart(parse_compressed(buf)?),
+            10 => BinaryAnnotation::ChangeColumnEndDelta(decode_signed(parse_compressed(buf)?)),
+            11 => {
+                // the code delta is packed into the low four bits
+                let operand = parse_compressed(buf)?;
+                BinaryAnnotation::ChangeCodeOffsetAndLineOffset(operand & 0xf, decode_signed(operand >> 4))
+            }
+            12 => {
+                let length = parse_compressed(buf)?;
+                BinaryAnnotation::ChangeCodeLengthAndCodeOffset(length, parse_compressed(buf)?)
+            }
+            13 => BinaryAnnotation::ChangeColumnEnd(parse_compressed(buf)?),
+            _ => return Err(Error::InvalidBinaryAnnotation("unknown opcode")),
+        };
+
+        Ok(Some(annotation))
+    }
+}
+
+/// A range of code whose length is not known until the next range starts.
+#[derive(Debug)]
+struct PendingLine {
+    offset: u32,
+    length: Option<u32>,
+    file_index: FileIndex,
+    line: u32,
+    kind: LineInfoKind,
+}
+
+/// An iterator over the lines of an inline site, produced by `InlineSiteSymbol::lines()`.
+///
+/// Each `LineInfo` maps a range of the inline site's code to a line of the inlined function.
+#[derive(Debug)]
+pub struct InlineSiteLineIter<'a> {
+    annotations: BinaryAnnotationIter<'a>,
+    segment: u16,
+    base: u32,
+
+    code_offset: u32,
+    code_length: Option<u32>,
+    file_index: FileIndex,
+    line: u32,
+    kind: LineInfoKind,
+
+    pending: Option<PendingLine>,
+}
+
+impl<'a> InlineSiteLineIter<'a> {
+    pub(crate) fn new(annotations: BinaryAnnotationIter<'a>, procedure: &ProcedureSymbol, inlinee: &InlineeLine) -> Self {
+        InlineSiteLineIter {
+            annotations,
+            segment: procedure.segment,
+            base: procedure.offset,
+            code_offset: 0,
+            code_length: None,
+            file_index: inlinee.file_index,
+            line: inlinee.line,
+            kind: LineInfoKind::Statement,
+            pending: None,
+        }
+    }
+
+    fn line_info(&self, pending: PendingLine) -> LineInfo {
+        LineInfo {
+            offset: self.base.wrapping_add(pending.offset),
+            segment: self.segment,
+            length: pending.length.unwrap_or(0),
+            file_index: pending.file_index,
+            line_start: pending.line,
+            line_end: pending.line,
+            column_start: None,
+            column_end: None,
+            kind: pending.kind,
+        }
+    }
+}
+
+impl<'a> FallibleIterator for InlineSiteLineIter<'a> {
+    type Item = LineInfo;
+    type Error = Error;
+
+    fn next(&mut self) -> result::Result<Option<Self::Item>, Self::Error> {
+        while let Some(annotation) = self.annotations.next()? {
+            match annotation {
+                BinaryAnnotation::CodeOffset(offset) => self.code_offset = offset,
+                BinaryAnnotation::ChangeCodeOffset(delta) => {
+                    self.code_offset = self.code_offset.wrapping_add(delta);
+                }
+                BinaryAnnotation::ChangeCodeLength(length) => {
+                    if let Some(ref mut pending) = self.pending {
+                        if pending.length.is_none() {
+                            pending.length = Some(length);
+                        }
+                    }
+                    self.code_offset = self.code_offset.wrapping_add(length);
+                }
+                BinaryAnnotation::ChangeFile(file_index) => self.file_index = file_index,
+                BinaryAnnotation::ChangeLineOffset(delta) => {
+                    self.line = (self.line as i32).wrapping_add(delta) as u32;
+                }
+                BinaryAnnotation::ChangeRangeKind(kind) => {
+                    self.kind = if kind == 0 { LineInfoKind::Expression } else { LineInfoKind::Statement };
+                }
+                BinaryAnnotation::ChangeCodeOffsetAndLineOffset(code_delta, line_delta) => {
+                    self.code_offset = self.code_offset.wrapping_add(code_delta);
+                    self.line = (self.line as i32).wrapping_add(line_delta) as u32;
+                }
+                BinaryAnnotation::ChangeCodeLengthAndCodeOffset(length, code_delta) => {
+                    self.code_length = Some(length);
+                    self.code_offset = self.code_offset.wrapping_add(code_delta);
+                }
+                // neither separated code nor columns are tracked
+                BinaryAnnotation::ChangeCodeOffsetBase(_) |
+                BinaryAnnotation::ChangeLineEndDelta(_) |
+                BinaryAnnotation::ChangeColumnStart(_) |
+                BinaryAnnotation::ChangeColumnEndDelta(_) |
+                BinaryAnnotation::ChangeColumnEnd(_) => (),
+            }
+
+            if !annotation.starts_range() {
+                continue;
+            }
+
+            // the new range ends the previous one, unless its length was already known
+            if let Some(ref mut pending) = self.pending {
+                if pending.length.is_none() {
+                    pending.length = Some(self.code_offset.wrapping_sub(pending.offset));
+                }
+            }
+
+            let line = PendingLine {
+                offset: self.code_offset,
+                length: self.code_length.take(),
+                file_index: self.file_index,
+                line: self.line,
+                kind: self.kind,
+            };
+
+            if let Some(previous) = self.pending.replace(line) {
+                return Ok(Some(self.line_info(previous)));
+            }
+        }
+
+        Ok(self.pending.take().map(|pending| self.line_info(pending)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod annotations {
+        use symbol::annotations::*;
+        use symbol::ProcedureFlags;
+
+        fn parse(data: &[u8]) -> Result<Vec<BinaryAnnotation>> {
+            BinaryAnnotations::new(data).iter().collect()
+        }
+
+        #[test]
+        fn test_parse() {
+            assert_eq!(parse(&[12, 36, 128, 251]).expect("parse"), vec![
+                BinaryAnnotation::ChangeCodeLengthAndCodeOffset(36, 251),
+            ]);
+
+            assert_eq!(parse(&[12, 5, 4, 6, 4, 12, 5, 9]).expect("parse"), vec![
+                BinaryAnnotation::ChangeCodeLengthAndCodeOffset(5, 4),
+                BinaryAnnotation::ChangeLineOffset(2),
+                BinaryAnnotation::ChangeCodeLengthAndCodeOffset(5, 9),
+            ]);
+
+            assert_eq!(parse(&[11, 0x33, 6, 3, 5, 0xc0, 0x01, 0x02, 0x03, 0, 0]).expect("parse"), vec![
+                BinaryAnnotation::ChangeCodeOffsetAndLineOffset(3, -1),
+                BinaryAnnotation::ChangeLineOffset(-1),
+                BinaryAnnotation::ChangeFile(0x10203),
+            ]);
+
+            match parse(&[3, 0xe0]) {
+                Err(Error::InvalidBinaryAnnotation(_)) => (),
+                other => panic!("unexpected {:?}", other),
+            }
+        }
+
+        #[test]
+        fn test_lines() {
+            let procedure = ProcedureSymbol {
+                global: true,
+                dpc: false,
+                parent: 0,
+                end: 0,
+                next: 0,
+                len: 0x40,
+                dbg_start_offset: 0,
+                dbg_end_offset: 0x40,
+                type_index: 0,
+                offset: 0x1000,
+                segment: 1,
+                flags: ProcedureFlags::default(),
+            };
+            let inlinee = InlineeLine { inlinee: 0x1000, file_index: 0x18, line: 10, extra_files: vec![] };
+
+            let data = &[3, 4, 11, 35, 5, 0x30, 6, 11, 12, 2, 6, 3, 8, 4, 4];
+            let annotations = BinaryAnnotations::new(data);
+            assert_eq!(parse(data).expect("parse"), vec![
+                BinaryAnnotation::ChangeCodeOffset(4),
+                BinaryAnnotation::ChangeCodeOffsetAndLineOffset(3, 1),
+                BinaryAnnotation::ChangeFile(0x30),
+                BinaryAnnotation::ChangeLineOffset(-5),
+                BinaryAnnotation::ChangeCodeLengthAndCodeOffset(2, 6),
+                BinaryAnnotation::ChangeCodeOffset(8),
+                BinaryAnnotation::ChangeCodeLength(4),
+            ]);
+
+            let lines: Vec<_> = InlineSiteLineIter::new(annotations.iter(), &procedure, &inlinee)
+                .map(|line| (line.offset, line.length, line.file_index, line.line_start))
+                .collect().expect("lines");
+
+            assert_eq!(lines, vec![
+                (0x1004, 3, 0x18, 10),
+                (0x1007, 6, 0x18, 11),
+                (0x100d, 2, 0x30, 6),
+                (0x1015, 4, 0x30, 6),
+            ]);
+        }
+    }
+}
@@ -0,0 +1,145 @@
+// Copyright 2017 pdb Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use common::*;
+use FallibleIterator;
+use super::{AddressGaps, AddressRange, Register, SymbolData, SymbolIter};
+use super::constants::*;
+
+/// Where a local variable lives at a particular point in the code.
+///
+/// Find it with `ModuleInfo::variable_location()`.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub enum VariableLocation {
+    /// The variable is in a register.
+    Register(Register),
+    /// The variable is in memory, at an offset from the address in a register.
+    RegisterRelative(Register, i32),
+    /// The variable is in memory, at an offset from the frame pointer.
+    ///
+    /// Which register holds the frame pointer depends on the procedure.
+    FramePointerRelative(i32),
+    /// The variable does not exist at this point, for example because it was optimized out.
+    Unavailable,
+}
+
+/// Returns true if symbols of this kind describe where the preceding `S_LOCAL` lives.
+fn is_def_range(kind: u16) -> bool {
+    matches!(kind,
+        S_DEFRANGE | S_DEFRANGE_SUBFIELD |
+        S_DEFRANGE_REGISTER | S_DEFRANGE_FRAMEPOINTER_REL |
+        S_DEFRANGE_SUBFIELD_REGISTER | S_DEFRANGE_FRAMEPOINTER_REL_FULL_SCOPE |
+        S_DEFRANGE_REGISTER_REL | S_DEFRANGE_HLSL | S_DEFRANGE_DPC_PTR_TAG)
+}
+
+/// Returns true if `segment:offset` lies within `range`, but not within any of its `gaps`.
+fn is_live(range: &AddressRange, gaps: &AddressGaps, segment: u16, offset: u32) -> bool {
+    if !range.contains(segment, offset) {
+        return false;
+    }
+
+    let relative = offset - range.offset;
+    !gaps.iter().any(|gap| {
+        relative >= u32::from(gap.offset) && relative - u32::from(gap.offset) < u32::from(gap.len)
+    })
+}
+
+/// Finds where the variable defined by the `S_LOCAL` at the start of `symbols` lives at the
+/// address `segment:offset`.
+///
+/// Locations which only describe a part of the variable, and `S_DEFRANGE` programs, are skipped.
+pub(crate) fn locate_variable(mut symbols: SymbolIter, segment: u16, offset: u32) -> Result<VariableLocation> {
+    let local = symbols.next()?.ok_or(Error::UnexpectedEof)?;
+    if local.raw_kind() != S_LOCAL {
+        return Err(Error::UnexpectedSymbolKind(local.raw_kind()));
+    }
+
+    // a full scope location applies anywhere the more specific ranges don't
+    let mut full_scope = VariableLocation::Unavailable;
+
+    while let Some(symbol) = symbols.next()? {
+        if !is_def_range(symbol.raw_kind()) {
+            break;
+        }
+
+        let data = match symbol.parse() {
+            Ok(data) => data,
+            Err(Error::UnimplementedSymbolKind(_)) => continue,
+            Err(e) => return Err(e),
+        };
+
+        match data {
+            SymbolData::DefRangeRegister(ref data) if is_live(&data.range, &data.gaps, segment, offset) => {
+                return Ok(VariableLocation::Register(data.register));
+            }
+            SymbolData::DefRangeFramePointerRelative(ref data) if is_live(&data.range, &data.gaps, segment, offset) => {
+                return Ok(VariableLocation::FramePointerRelative(data.offset));
+            }
+            SymbolData::DefRangeRegisterRelative(ref data)
+                if !data.spilled_udt_member && is_live(&data.range, &data.gaps, segment, offset) => {
+                return Ok(VariableLocation::RegisterRelative(data.register, data.offset));
+            }
+            SymbolData::DefRangeFramePointerRelativeFullScope(ref data) => {
+                full_scope = VariableLocation::FramePointerRelative(data.offset);
+            }
+            _ => (),
+        }
+    }
+
+    Ok(full_scope)
+}
+
+#[cfg(test)]
+mod tests {
+    mod locations {
+        use symbol::location::*;
+
+        fn symbols(records: &[&[u8]]) -> Vec<u8> {
+            let mut data = Vec::new();
+            for record in records {
+                data.push(record.len() as u8);
+                data.push(0);
+                data.extend_from_slice(record);
+            }
+            data
+        }
+
+        fn locate(data: &[u8], offset: u32) -> Result<VariableLocation> {
+            locate_variable(SymbolIter::new(ParseBuffer::from(data)), 1, offset)
+        }
+
+        #[test]
+        fn test_locate_variable() {
+            let data = symbols(&[
+                // S_LOCAL has_cctor
+                &[62, 17, 48, 0, 0, 0, 0, 0, 104, 97, 115, 95, 99, 99, 116, 111, 114, 0],
+                // S_DEFRANGE_REGISTER SIL, 1:0x5807 + 0x77, gap at +5 of 0x6d bytes
+                &[65, 17, 68, 1, 0, 0, 7, 88, 0, 0, 1, 0, 119, 0, 5, 0, 109, 0],
+                // S_DEFRANGE_FRAMEPOINTER_REL_FULL_SCOPE 32
+                &[68, 17, 32, 0, 0, 0],
+                // S_LOCAL main_result
+                &[62, 17, 242, 16, 0, 0, 0, 0, 109, 97, 105, 110, 95, 114, 101, 115, 117, 108, 116, 0],
+            ]);
+
+            assert_eq!(locate(&data, 0x5807).expect("locate"), VariableLocation::Register(Register(324)));
+            assert_eq!(locate(&data, 0x580b).expect("locate"), VariableLocation::Register(Register(324)));
+            assert_eq!(locate(&data, 0x580c).expect("locate"), VariableLocation::FramePointerRelative(32));
+            assert_eq!(locate(&data, 0x5878).expect("locate"), VariableLocation::FramePointerRelative(32));
+            assert_eq!(locate(&data, 0x5879).expect("locate"), VariableLocation::Register(Register(324)));
+            assert_eq!(locate(&data, 0x587d).expect("locate"), VariableLocation::Register(Register(324)));
+            assert_eq!(locate(&data, 0x587e).expect("locate"), VariableLocation::FramePointerRelative(32));
+
+            // main_result has no locations at all
+            assert_eq!(locate(&data[48..], 0x5807).expect("locate"), VariableLocation::Unavailable);
+
+            match locate(&data[20..], 0x5807) {
+                Err(Error::UnexpectedSymbolKind(0x1141)) => (),
+                other => panic!("unexpected {:?}", other),
+            }
+        }
+    }
+}
@@ -7,13 +7,24 @@
 
 use std::fmt;
 use std::result;
+use std::slice;
 use FallibleIterator;
 
 use common::*;
+use module_info::InlineeLine;
 use msf::*;
 
-mod constants;
+mod annotations;
+pub(crate) mod constants;
+mod location;
+mod register;
+mod scope;
 use self::constants::*;
+pub use self::annotations::{BinaryAnnotation, BinaryAnnotationIter, BinaryAnnotations, InlineSiteLineIter};
+pub use self::location::VariableLocation;
+pub(crate) use self::location::locate_variable;
+pub use self::register::{Register, X86Register, AMD64Register, ARM64Register};
+pub use self::scope::{ScopedSymbol, ScopedSymbolIter};
 
 /// PDB symbol tables contain names, locations, and metadata about functions, global/static data,
 /// constants, data types, and more.
@@ -118,6 +129,48 @@ impl<'t> Symbol<'t> {
             S_LTHREAD32 | S_LTHREAD32_ST |
             S_GTHREAD32 | S_GTHREAD32_ST => 10,
 
+            S_LPROC32 | S_LPROC32_ST |
+            S_GPROC32 | S_GPROC32_ST |
+            S_LPROC32_ID | S_GPROC32_ID |
+            S_LPROC32_DPC | S_LPROC32_DPC_ID => 35,
+
+            S_BPREL32 | S_BPREL32_ST => 8,
+
+            S_REGREL32 | S_REGREL32_ST => 10,
+
+            S_REGISTER | S_REGISTER_ST => 6,
+
+            S_LOCAL => 6,
+
+            // these have no name, and end in a variable number of gaps
+            S_DEFRANGE |
+            S_DEFRANGE_REGISTER |
+            S_DEFRANGE_FRAMEPOINTER_REL |
+            S_DEFRANGE_SUBFIELD_REGISTER |
+            S_DEFRANGE_FRAMEPOINTER_REL_FULL_SCOPE |
+            S_DEFRANGE_REGISTER_REL => return Ok(self.0.len() - 2),
+
+            // these have no name, and end in a variable number of binary annotations
+            S_INLINESITE | S_INLINESITE2 => return Ok(self.0.len() - 2),
+
+            S_OBJNAME | S_OBJNAME_ST => 4,
+
+            S_COMPILE2 | S_COMPILE2_ST => 18,
+
+            S_COMPILE3 => 22,
+
+            // this has no name, and ends in a list of strings
+            S_ENVBLOCK => return Ok(self.0.len() - 2),
+
+            // these have no name
+            S_BUILDINFO | S_FRAMEPROC | S_TRAMPOLINE => return Ok(self.0.len() - 2),
+
+            S_THUNK32 | S_THUNK32_ST => 21,
+
+            S_LABEL32 | S_LABEL32_ST => 7,
+
+            S_BLOCK32 | S_BLOCK32_ST => 18,
+
             _ => return Err(Error::UnimplementedSymbolKind(kind))
         };
 
@@ -130,8 +183,14 @@ impl<'t> Symbol<'t> {
 
     /// Parse the symbol into the `SymbolData` it contains.
     #[inline]
-    pub fn parse(&self) -> Result<SymbolData> {
-        parse_symbol_data(self.raw_kind(), self.field_data()?)
+    pub fn parse(&self) -> Result<SymbolData<'t>> {
+        match self.raw_kind() {
+            // thunks carry further fields after their name
+            S_THUNK32 | S_THUNK32_ST => {
+                parse_thunk_symbol(self.raw_kind(), self.field_data()?, self.variant_data()?)
+            }
+            kind => parse_symbol_data(kind, self.field_data()?),
+        }
     }
 
     /// Returns a slice containing the field information describing this symbol but not including
@@ -146,21 +205,32 @@ impl<'t> Symbol<'t> {
     /// Returns the name of the symbol. Note that the underlying buffer is owned by the
     /// `SymbolTable`.
     pub fn name(&self) -> Result<RawString<'t>> {
-        // figure out how long the data is
-        let data_length = self.data_length()?;
-
         // figure out where the name is
-        let mut buf = ParseBuffer::from(&self.0[2 + data_length ..]);
+        let mut buf = ParseBuffer::from(&self.0[2 + self.data_length()? ..]);
+        self.parse_name(&mut buf)
+    }
+
+    /// Returns a slice containing the data following the symbol's name.
+    fn variant_data(&self) -> Result<&'t [u8]> {
+        let mut buf = ParseBuffer::from(&self.0[2 + self.data_length()? ..]);
+        self.parse_name(&mut buf)?;
+        let len = buf.len();
+        buf.take(len)
+    }
+
+    fn parse_name(&self, buf: &mut ParseBuffer<'t>) -> Result<RawString<'t>> {
+        // some symbols have no name at all
+        if buf.len() == 0 {
+            return Ok(RawString::from(""));
+        }
 
         // names come in two varieties:
         if self.raw_kind() < S_ST_MAX {
             // Pascal-style name
-            let name = buf.parse_u8_pascal_string()?;
-            Ok(name)
+            buf.parse_u8_pascal_string()
         } else {
             // NUL-terminated name
-            let name = buf.parse_cstring()?;
-            Ok(name)
+            buf.parse_cstring()
         }
     }
 }
@@ -184,7 +254,143 @@ const CVPSF_FUNCTION: u32 = 0x00000002;
 const CVPSF_MANAGED: u32 = 0x00000004;
 const CVPSF_MSIL: u32 = 0x00000008;
 
-fn parse_symbol_data(kind: u16, data: &[u8]) -> Result<SymbolData> {
+// CV_PROCFLAGS:
+const CV_PFLAG_NOFPO: u8 = 0x01;
+const CV_PFLAG_INT: u8 = 0x02;
+const CV_PFLAG_FAR: u8 = 0x04;
+const CV_PFLAG_NEVER: u8 = 0x08;
+const CV_PFLAG_NOTREACHED: u8 = 0x10;
+const CV_PFLAG_CUST_CALL: u8 = 0x20;
+const CV_PFLAG_NOINLINE: u8 = 0x40;
+const CV_PFLAG_OPTDBGINFO: u8 = 0x80;
+
+// CV_LVARFLAGS:
+const CV_LVARFLAG_ISPARAM: u16 = 0x0001;
+const CV_LVARFLAG_ADDRTAKEN: u16 = 0x0002;
+const CV_LVARFLAG_COMPGENX: u16 = 0x0004;
+const CV_LVARFLAG_ISAGGREGATE: u16 = 0x0008;
+const CV_LVARFLAG_ISAGGREGATED: u16 = 0x0010;
+const CV_LVARFLAG_ISALIASED: u16 = 0x0020;
+const CV_LVARFLAG_ISALIAS: u16 = 0x0040;
+const CV_LVARFLAG_ISRETVALUE: u16 = 0x0080;
+const CV_LVARFLAG_ISOPTIMIZEDOUT: u16 = 0x0100;
+const CV_LVARFLAG_ISENREG_GLOB: u16 = 0x0200;
+const CV_LVARFLAG_ISENREG_STAT: u16 = 0x0400;
+
+// COMPILESYM3 flags, after the language in the lowest byte:
+const CV_COMPILE_EC: u32 = 0x0000_0100;
+const CV_COMPILE_NODBGINFO: u32 = 0x0000_0200;
+const CV_COMPILE_LTCG: u32 = 0x0000_0400;
+const CV_COMPILE_NODATAALIGN: u32 = 0x0000_0800;
+const CV_COMPILE_MANAGEDPRESENT: u32 = 0x0000_1000;
+const CV_COMPILE_SECURITYCHECKS: u32 = 0x0000_2000;
+const CV_COMPILE_HOTPATCH: u32 = 0x0000_4000;
+const CV_COMPILE_CVTCIL: u32 = 0x0000_8000;
+const CV_COMPILE_MSILMODULE: u32 = 0x0001_0000;
+const CV_COMPILE_SDL: u32 = 0x0002_0000;
+const CV_COMPILE_PGO: u32 = 0x0004_0000;
+const CV_COMPILE_EXP: u32 = 0x0008_0000;
+
+// FRAMEPROCSYM flags:
+const CV_FPFLAG_HASALLOCA: u32 = 0x0000_0001;
+const CV_FPFLAG_HASSETJMP: u32 = 0x0000_0002;
+const CV_FPFLAG_HASLONGJMP: u32 = 0x0000_0004;
+const CV_FPFLAG_HASINLASM: u32 = 0x0000_0008;
+const CV_FPFLAG_HASEH: u32 = 0x0000_0010;
+const CV_FPFLAG_INLSPEC: u32 = 0x0000_0020;
+const CV_FPFLAG_HASSEH: u32 = 0x0000_0040;
+const CV_FPFLAG_NAKED: u32 = 0x0000_0080;
+const CV_FPFLAG_SECURITYCHECKS: u32 = 0x0000_0100;
+const CV_FPFLAG_ASYNCEH: u32 = 0x0000_0200;
+const CV_FPFLAG_GSNOSTACKORDERING: u32 = 0x0000_0400;
+const CV_FPFLAG_WASINLINED: u32 = 0x0000_0800;
+const CV_FPFLAG_GSCHECK: u32 = 0x0000_1000;
+const CV_FPFLAG_SAFEBUFFERS: u32 = 0x0000_2000;
+const CV_FPFLAG_LOCALBASEPOINTER_SHIFT: u32 = 14;
+const CV_FPFLAG_PARAMBASEPOINTER_SHIFT: u32 = 16;
+const CV_FPFLAG_POGOON: u32 = 0x0004_0000;
+const CV_FPFLAG_VALIDCOUNTS: u32 = 0x0008_0000;
+const CV_FPFLAG_OPTSPEED: u32 = 0x0010_0000;
+const CV_FPFLAG_GUARDCF: u32 = 0x0020_0000;
+const CV_FPFLAG_GUARDCFW: u32 = 0x0040_0000;
+
+// CV_RANGEATTR:
+const CV_RANGEATTR_MAYBE: u16 = 0x0001;
+
+fn parse_compile_flags(buf: &mut ParseBuffer, kind: u16) -> Result<CompileFlagsSymbol> {
+    let mut flags = buf.parse_u32()?;
+    if kind != S_COMPILE3 {
+        // these bits are padding in COMPILESYM
+        flags &= !(CV_COMPILE_SDL | CV_COMPILE_PGO | CV_COMPILE_EXP);
+    }
+
+    let cpu_type = CPUType::from(buf.parse_u16()?);
+    let frontend_version = CompilerVersion {
+        major: buf.parse_u16()?,
+        minor: buf.parse_u16()?,
+        build: buf.parse_u16()?,
+        qfe: match kind { S_COMPILE3 => buf.parse_u16()?, _ => 0 },
+    };
+    let backend_version = CompilerVersion {
+        major: buf.parse_u16()?,
+        minor: buf.parse_u16()?,
+        build: buf.parse_u16()?,
+        qfe: match kind { S_COMPILE3 => buf.parse_u16()?, _ => 0 },
+    };
+
+    Ok(CompileFlagsSymbol {
+        language: SourceLanguage::from(flags as u8),
+        flags: CompileFlags::from(flags),
+        cpu_type,
+        frontend_version,
+        backend_version,
+    })
+}
+
+
+fn parse_address_range(buf: &mut ParseBuffer) -> Result<AddressRange> {
+    Ok(AddressRange {
+        offset: buf.parse_u32()?,
+        segment: buf.parse_u16()?,
+        len: buf.parse_u16()?,
+    })
+}
+
+fn parse_address_gaps<'t>(buf: &mut ParseBuffer<'t>) -> Result<AddressGaps<'t>> {
+    // each gap is four bytes; ignore any trailing bytes
+    let len = buf.len() - buf.len() % 4;
+    Ok(AddressGaps(buf.take(len)?))
+}
+
+fn parse_thunk_symbol<'t>(kind: u16, data: &[u8], variant: &'t [u8]) -> Result<SymbolData<'t>> {
+    let mut buf = ParseBuffer::from(data);
+    let parent = buf.parse_u32()?;
+    let end = buf.parse_u32()?;
+    let next = buf.parse_u32()?;
+    let offset = buf.parse_u32()?;
+    let segment = buf.parse_u16()?;
+    let len = buf.parse_u16()?;
+    let ordinal = buf.parse_u8()?;
+
+    let mut buf = ParseBuffer::from(variant);
+    let thunk_kind = match ordinal {
+        0 => ThunkKind::NoType,
+        1 => ThunkKind::Adjustor {
+            delta: buf.parse_i16()?,
+            target: if kind < S_ST_MAX { buf.parse_u8_pascal_string()? } else { buf.parse_cstring()? },
+        },
+        2 => ThunkKind::VCall { vtable_offset: buf.parse_i16()? },
+        3 => ThunkKind::PCode { offset: buf.parse_u32()?, segment: buf.parse_u16()? },
+        4 => ThunkKind::Load,
+        5 => ThunkKind::TrampolineIncremental,
+        6 => ThunkKind::TrampolineBranchIsland,
+        _ => ThunkKind::OtherValue(ordinal),
+    };
+
+    Ok(SymbolData::Thunk(ThunkSymbol { parent, end, next, offset, segment, len, kind: thunk_kind }))
+}
+
+fn parse_symbol_data(kind: u16, data: &[u8]) -> Result<SymbolData<'_>> {
     let mut buf = ParseBuffer::from(data);
 
     match kind {
@@ -262,13 +468,194 @@ fn parse_symbol_data(kind: u16, data: &[u8]) -> Result<SymbolData> {
             }))
         }
 
+        S_LPROC32 | S_LPROC32_ST |
+        S_GPROC32 | S_GPROC32_ST |
+        S_LPROC32_ID | S_GPROC32_ID |
+        S_LPROC32_DPC | S_LPROC32_DPC_ID => {
+            Ok(SymbolData::Procedure(ProcedureSymbol {
+                global: match kind { S_GPROC32 | S_GPROC32_ST | S_GPROC32_ID => true, _ => false },
+                dpc: match kind { S_LPROC32_DPC | S_LPROC32_DPC_ID => true, _ => false },
+                parent: buf.parse_u32()?,
+                end: buf.parse_u32()?,
+                next: buf.parse_u32()?,
+                len: buf.parse_u32()?,
+                dbg_start_offset: buf.parse_u32()?,
+                dbg_end_offset: buf.parse_u32()?,
+                type_index: buf.parse_u32()?,
+                offset: buf.parse_u32()?,
+                segment: buf.parse_u16()?,
+                flags: ProcedureFlags::from(buf.parse_u8()?),
+            }))
+        }
+
+        S_BPREL32 | S_BPREL32_ST => {
+            Ok(SymbolData::BasePointerRelative(BasePointerRelativeSymbol {
+                offset: buf.parse_i32()?,
+                type_index: buf.parse_u32()?,
+            }))
+        }
+
+        S_REGREL32 | S_REGREL32_ST => {
+            Ok(SymbolData::RegisterRelative(RegisterRelativeSymbol {
+                offset: buf.parse_i32()?,
+                type_index: buf.parse_u32()?,
+                register: Register(buf.parse_u16()?),
+            }))
+        }
+
+        S_REGISTER | S_REGISTER_ST => {
+            Ok(SymbolData::RegisterVariable(RegisterVariableSymbol {
+                type_index: buf.parse_u32()?,
+                register: Register(buf.parse_u16()?),
+            }))
+        }
+
+        S_LOCAL => {
+            Ok(SymbolData::Local(LocalSymbol {
+                type_index: buf.parse_u32()?,
+                flags: LocalVariableFlags::from(buf.parse_u16()?),
+            }))
+        }
+
+        S_DEFRANGE => {
+            Ok(SymbolData::DefRange(DefRangeSymbol {
+                program: buf.parse_u32()?,
+                range: parse_address_range(&mut buf)?,
+                gaps: parse_address_gaps(&mut buf)?,
+            }))
+        }
+
+        S_DEFRANGE_REGISTER => {
+            Ok(SymbolData::DefRangeRegister(DefRangeRegisterSymbol {
+                register: Register(buf.parse_u16()?),
+                may_have_no_user_name: buf.parse_u16()? & CV_RANGEATTR_MAYBE != 0,
+                range: parse_address_range(&mut buf)?,
+                gaps: parse_address_gaps(&mut buf)?,
+            }))
+        }
+
+        S_DEFRANGE_FRAMEPOINTER_REL => {
+            Ok(SymbolData::DefRangeFramePointerRelative(DefRangeFramePointerRelativeSymbol {
+                offset: buf.parse_i32()?,
+                range: parse_address_range(&mut buf)?,
+                gaps: parse_address_gaps(&mut buf)?,
+            }))
+        }
+
+        S_DEFRANGE_SUBFIELD_REGISTER => {
+            Ok(SymbolData::DefRangeSubfieldRegister(DefRangeSubfieldRegisterSymbol {
+                register: Register(buf.parse_u16()?),
+                may_have_no_user_name: buf.parse_u16()? & CV_RANGEATTR_MAYBE != 0,
+                parent_offset: buf.parse_u32()? & 0xfff,
+                range: parse_address_range(&mut buf)?,
+                gaps: parse_address_gaps(&mut buf)?,
+            }))
+        }
+
+        S_DEFRANGE_FRAMEPOINTER_REL_FULL_SCOPE => {
+            Ok(SymbolData::DefRangeFramePointerRelativeFullScope(DefRangeFramePointerRelativeFullScopeSymbol {
+                offset: buf.parse_i32()?,
+            }))
+        }
+
+        S_DEFRANGE_REGISTER_REL => {
+            let register = Register(buf.parse_u16()?);
+            let flags = buf.parse_u16()?;
+            Ok(SymbolData::DefRangeRegisterRelative(DefRangeRegisterRelativeSymbol {
+                register,
+                spilled_udt_member: flags & 0x1 != 0,
+                parent_offset: flags >> 4,
+                offset: buf.parse_i32()?,
+                range: parse_address_range(&mut buf)?,
+                gaps: parse_address_gaps(&mut buf)?,
+            }))
+        }
+
+        S_OBJNAME | S_OBJNAME_ST => {
+            Ok(SymbolData::ObjName(ObjNameSymbol {
+                signature: buf.parse_u32()?,
+            }))
+        }
+
+        S_COMPILE2 | S_COMPILE2_ST | S_COMPILE3 => {
+            Ok(SymbolData::CompileFlags(parse_compile_flags(&mut buf, kind)?))
+        }
+
+        S_ENVBLOCK => {
+            // a reserved byte of flags
+            buf.parse_u8()?;
+            Ok(SymbolData::EnvBlock(EnvBlockSymbol {
+                data: buf.take(buf.len())?,
+            }))
+        }
+
+        S_FRAMEPROC => {
+            Ok(SymbolData::FrameProcedure(FrameProcedureSymbol {
+                frame_size: buf.parse_u32()?,
+                padding_size: buf.parse_u32()?,
+                padding_offset: buf.parse_u32()?,
+                saved_registers_size: buf.parse_u32()?,
+                exception_handler_offset: buf.parse_u32()?,
+                exception_handler_section: buf.parse_u16()?,
+                flags: FrameProcedureFlags::from(buf.parse_u32()?),
+            }))
+        }
+
+        S_TRAMPOLINE => {
+            Ok(SymbolData::Trampoline(TrampolineSymbol {
+                kind: TrampolineKind::from(buf.parse_u16()?),
+                size: buf.parse_u16()?,
+                thunk_offset: buf.parse_u32()?,
+                target_offset: buf.parse_u32()?,
+                thunk_segment: buf.parse_u16()?,
+                target_segment: buf.parse_u16()?,
+            }))
+        }
+
+        S_LABEL32 | S_LABEL32_ST => {
+            Ok(SymbolData::Label(LabelSymbol {
+                offset: buf.parse_u32()?,
+                segment: buf.parse_u16()?,
+                flags: ProcedureFlags::from(buf.parse_u8()?),
+            }))
+        }
+
+        S_BLOCK32 | S_BLOCK32_ST => {
+            Ok(SymbolData::Block(BlockSymbol {
+                parent: buf.parse_u32()?,
+                end: buf.parse_u32()?,
+                len: buf.parse_u32()?,
+                offset: buf.parse_u32()?,
+                segment: buf.parse_u16()?,
+            }))
+        }
+
+        S_BUILDINFO => {
+            Ok(SymbolData::BuildInfo(BuildInfoSymbol {
+                id: buf.parse_u32()?,
+            }))
+        }
+
+        S_INLINESITE | S_INLINESITE2 => {
+            Ok(SymbolData::InlineSite(InlineSiteSymbol {
+                parent: buf.parse_u32()?,
+                end: buf.parse_u32()?,
+                inlinee: buf.parse_u32()?,
+                invocations: match kind { S_INLINESITE2 => Some(buf.parse_u32()?), _ => None },
+                annotations: BinaryAnnotations::new(buf.take(buf.len())?),
+            }))
+        }
+
         _ => Err(Error::UnimplementedSymbolKind(kind))
     }
 }
 
 /// `SymbolData` contains the information parsed from a symbol record.
+///
+/// Variable-length parts of a record, such as the gaps of an `S_DEFRANGE_*` symbol, are not
+/// decoded up front; `SymbolData` borrows them from the record instead.
 #[derive(Debug,Copy,Clone,Eq,PartialEq)]
-pub enum SymbolData {
+pub enum SymbolData<'t> {
     // S_PUB32 (0x110e) | S_PUB32_ST (0x1009)
     PublicSymbol(PublicSymbol),
 
@@ -297,6 +684,93 @@ pub enum SymbolData {
     // S_LTHREAD32 (0x1112) | S_LTHREAD32_ST (0x100e)
     // S_GTHREAD32 (0x1113) | S_GTHREAD32_ST (0x100f)
     ThreadStorage(ThreadStorageSymbol),
+
+    //     S_LPROC32 (0x110f) | S_LPROC32_ST (0x100a)
+    //     S_GPROC32 (0x1110) | S_GPROC32_ST (0x100b)
+    //  S_LPROC32_ID (0x1146) | S_GPROC32_ID (0x1147)
+    // S_LPROC32_DPC (0x1155) | S_LPROC32_DPC_ID (0x1156)
+    Procedure(ProcedureSymbol),
+
+    //   S_BPREL32 (0x110b) | S_BPREL32_ST (0x1006)
+    BasePointerRelative(BasePointerRelativeSymbol),
+
+    //  S_REGREL32 (0x1111) | S_REGREL32_ST (0x100d)
+    RegisterRelative(RegisterRelativeSymbol),
+
+    //  S_REGISTER (0x1106) | S_REGISTER_ST (0x1001)
+    RegisterVariable(RegisterVariableSymbol),
+
+    //     S_LOCAL (0x113e)
+    Local(LocalSymbol),
+
+    // S_DEFRANGE (0x113f)
+    DefRange(DefRangeSymbol<'t>),
+
+    // S_DEFRANGE_REGISTER (0x1141)
+    DefRangeRegister(DefRangeRegisterSymbol<'t>),
+
+    // S_DEFRANGE_FRAMEPOINTER_REL (0x1142)
+    DefRangeFramePointerRelative(DefRangeFramePointerRelativeSymbol<'t>),
+
+    // S_DEFRANGE_SUBFIELD_REGISTER (0x1143)
+    DefRangeSubfieldRegister(DefRangeSubfieldRegisterSymbol<'t>),
+
+    // S_DEFRANGE_FRAMEPOINTER_REL_FULL_SCOPE (0x1144)
+    DefRangeFramePointerRelativeFullScope(DefRangeFramePointerRelativeFullScopeSymbol),
+
+    // S_DEFRANGE_REGISTER_REL (0x1145)
+    DefRangeRegisterRelative(DefRangeRegisterRelativeSymbol<'t>),
+
+    // S_INLINESITE (0x114d) | S_INLINESITE2 (0x115d)
+    InlineSite(InlineSiteSymbol<'t>),
+
+    //   S_OBJNAME (0x1101) | S_OBJNAME_ST (0x0009)
+    ObjName(ObjNameSymbol),
+
+    //  S_COMPILE2 (0x1116) | S_COMPILE2_ST (0x1013)
+    //  S_COMPILE3 (0x113c)
+    CompileFlags(CompileFlagsSymbol),
+
+    //  S_ENVBLOCK (0x113d)
+    EnvBlock(EnvBlockSymbol<'t>),
+
+    // S_BUILDINFO (0x114c)
+    BuildInfo(BuildInfoSymbol),
+
+    // S_FRAMEPROC (0x1012)
+    FrameProcedure(FrameProcedureSymbol),
+
+    //   S_THUNK32 (0x1102) | S_THUNK32_ST (0x0206)
+    Thunk(ThunkSymbol<'t>),
+
+    // S_TRAMPOLINE (0x112c)
+    Trampoline(TrampolineSymbol),
+
+    //   S_LABEL32 (0x1105) | S_LABEL32_ST (0x0209)
+    Label(LabelSymbol),
+
+    //   S_BLOCK32 (0x1103) | S_BLOCK32_ST (0x0207)
+    Block(BlockSymbol),
+}
+
+impl<'t> SymbolData<'t> {
+    /// Returns the `segment:offset` address of this symbol, if it refers to a location in the
+    /// executable.
+    ///
+    /// Use an `AddressTranslator` to convert this into an RVA.
+    pub fn segment_offset(&self) -> Option<(u16, u32)> {
+        match *self {
+            SymbolData::PublicSymbol(ref data) => Some((data.segment, data.offset)),
+            SymbolData::DataSymbol(ref data) => Some((data.segment, data.offset)),
+            SymbolData::ThreadStorage(ref data) => Some((data.segment, data.offset)),
+            SymbolData::Procedure(ref data) => Some((data.segment, data.offset)),
+            SymbolData::Thunk(ref data) => Some((data.segment, data.offset)),
+            SymbolData::Trampoline(ref data) => Some((data.thunk_segment, data.thunk_offset)),
+            SymbolData::Label(ref data) => Some((data.segment, data.offset)),
+            SymbolData::Block(ref data) => Some((data.segment, data.offset)),
+            _ => None,
+        }
+    }
 }
 
 /// The information parsed from a symbol record with kind `S_PUB32` or `S_PUB32_ST`.
@@ -371,6 +845,811 @@ pub struct ThreadStorageSymbol {
     pub segment: u16,
 }
 
+/// The information parsed from a symbol record with kind
+/// `S_LPROC32`, `S_LPROC32_ST`, `S_GPROC32`, `S_GPROC32_ST`, `S_LPROC32_ID`, `S_GPROC32_ID`,
+/// `S_LPROC32_DPC`, or `S_LPROC32_DPC_ID`.
+///
+/// A procedure opens a scope, which is closed by the `S_END` symbol at `end`. `parent`, `end`, and
+/// `next` are byte offsets of other symbols in the same module info stream, or `0` for none.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct ProcedureSymbol {
+    pub global: bool,
+    /// Whether this is a deferred procedure call, i.e. `S_LPROC32_DPC` or `S_LPROC32_DPC_ID`.
+    pub dpc: bool,
+    /// The enclosing scope, if any.
+    pub parent: u32,
+    /// The `S_END` symbol closing this procedure's scope.
+    pub end: u32,
+    /// The next procedure in the module, if the compiler recorded it.
+    pub next: u32,
+    /// The length of the procedure's code in bytes.
+    pub len: u32,
+    /// The offset from the start of the procedure to the end of its prologue.
+    pub dbg_start_offset: u32,
+    /// The offset from the start of the procedure to the start of its epilogue.
+    pub dbg_end_offset: u32,
+    /// The procedure's type, or for the `_ID` kinds, the `ItemIndex` of its `LF_FUNC_ID` or
+    /// `LF_MFUNC_ID` record.
+    pub type_index: TypeIndex,
+    pub offset: u32,
+    pub segment: u16,
+    pub flags: ProcedureFlags,
+}
+
+/// The `CV_PROCFLAGS` of a `ProcedureSymbol`.
+#[derive(Debug,Copy,Clone,Default,Eq,PartialEq)]
+pub struct ProcedureFlags {
+    /// The procedure has a frame pointer.
+    pub nofpo: bool,
+    /// The procedure returns with an interrupt return.
+    pub int: bool,
+    /// The procedure returns with a far return.
+    pub far: bool,
+    /// The procedure does not return.
+    pub never: bool,
+    /// The procedure is never called.
+    pub notreached: bool,
+    /// The procedure uses a custom calling convention.
+    pub cust_call: bool,
+    /// The procedure was marked as `noinline`.
+    pub noinline: bool,
+    /// The procedure has debug information for optimized code.
+    pub optdbginfo: bool,
+}
+
+impl From<u8> for ProcedureFlags {
+    fn from(flags: u8) -> Self {
+        ProcedureFlags {
+            nofpo: flags & CV_PFLAG_NOFPO != 0,
+            int: flags & CV_PFLAG_INT != 0,
+            far: flags & CV_PFLAG_FAR != 0,
+            never: flags & CV_PFLAG_NEVER != 0,
+            notreached: flags & CV_PFLAG_NOTREACHED != 0,
+            cust_call: flags & CV_PFLAG_CUST_CALL != 0,
+            noinline: flags & CV_PFLAG_NOINLINE != 0,
+            optdbginfo: flags & CV_PFLAG_OPTDBGINFO != 0,
+        }
+    }
+}
+
+/// The information parsed from a symbol record with kind `S_BPREL32` or `S_BPREL32_ST`.
+///
+/// This is a local variable or parameter stored relative to the frame pointer.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct BasePointerRelativeSymbol {
+    pub offset: i32,
+    pub type_index: TypeIndex,
+}
+
+/// The information parsed from a symbol record with kind `S_REGREL32` or `S_REGREL32_ST`.
+///
+/// This is a local variable or parameter stored at `offset` from the address in `register`.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct RegisterRelativeSymbol {
+    pub offset: i32,
+    pub type_index: TypeIndex,
+    pub register: Register,
+}
+
+/// The information parsed from a symbol record with kind `S_REGISTER` or `S_REGISTER_ST`.
+///
+/// This is a local variable or parameter which lives in `register` for its entire scope.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct RegisterVariableSymbol {
+    pub type_index: TypeIndex,
+    pub register: Register,
+}
+
+/// The information parsed from a symbol record with kind `S_LOCAL`.
+///
+/// Optimized code describes local variables with an `S_LOCAL` symbol, followed by `S_DEFRANGE_*`
+/// symbols giving the variable's location at different points in the code.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct LocalSymbol {
+    pub type_index: TypeIndex,
+    pub flags: LocalVariableFlags,
+}
+
+/// The `CV_LVARFLAGS` of a `LocalSymbol`.
+#[derive(Debug,Copy,Clone,Default,Eq,PartialEq)]
+pub struct LocalVariableFlags {
+    /// The variable is a parameter.
+    pub is_param: bool,
+    /// The address of the variable is taken.
+    pub address_taken: bool,
+    /// The variable was generated by the compiler.
+    pub compiler_generated: bool,
+    /// The variable is split into several parts, which are described by separate symbols.
+    pub aggregate: bool,
+    /// The variable is one of the parts of an aggregate variable.
+    pub aggregated: bool,
+    /// The variable has multiple simultaneous lifetimes.
+    pub aliased: bool,
+    /// The variable is one of the lifetimes of an aliased variable.
+    pub alias: bool,
+    /// The variable holds the return value of the procedure.
+    pub return_value: bool,
+    /// The variable has no location, because it was optimized out.
+    pub optimized_out: bool,
+    /// The variable is an enregistered global.
+    pub enreg_global: bool,
+    /// The variable is an enregistered static.
+    pub enreg_static: bool,
+}
+
+impl From<u16> for LocalVariableFlags {
+    fn from(flags: u16) -> Self {
+        LocalVariableFlags {
+            is_param: flags & CV_LVARFLAG_ISPARAM != 0,
+            address_taken: flags & CV_LVARFLAG_ADDRTAKEN != 0,
+            compiler_generated: flags & CV_LVARFLAG_COMPGENX != 0,
+            aggregate: flags & CV_LVARFLAG_ISAGGREGATE != 0,
+            aggregated: flags & CV_LVARFLAG_ISAGGREGATED != 0,
+            aliased: flags & CV_LVARFLAG_ISALIASED != 0,
+            alias: flags & CV_LVARFLAG_ISALIAS != 0,
+            return_value: flags & CV_LVARFLAG_ISRETVALUE != 0,
+            optimized_out: flags & CV_LVARFLAG_ISOPTIMIZEDOUT != 0,
+            enreg_global: flags & CV_LVARFLAG_ISENREG_GLOB != 0,
+            enreg_static: flags & CV_LVARFLAG_ISENREG_STAT != 0,
+        }
+    }
+}
+
+/// The range of code in which an `S_DEFRANGE_*` symbol applies, i.e. a `CV_LVAR_ADDR_RANGE`.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct AddressRange {
+    pub offset: u32,
+    pub segment: u16,
+    pub len: u16,
+}
+
+impl AddressRange {
+    /// Returns true if the range contains the address `segment:offset`.
+    pub fn contains(&self, segment: u16, offset: u32) -> bool {
+        segment == self.segment && offset >= self.offset && offset - self.offset < u32::from(self.len)
+    }
+}
+
+/// A part of an `AddressRange` in which an `S_DEFRANGE_*` symbol does not apply, i.e. a
+/// `CV_LVAR_ADDR_GAP`.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct AddressGap {
+    /// The start of the gap, relative to the start of the range.
+    pub offset: u16,
+    pub len: u16,
+}
+
+/// The `AddressGap`s at the end of an `S_DEFRANGE_*` symbol, borrowed from the symbol record.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct AddressGaps<'t>(&'t [u8]);
+
+impl<'t> AddressGaps<'t> {
+    /// Returns the number of gaps.
+    pub fn len(&self) -> usize {
+        self.0.len() / 4
+    }
+
+    /// Returns true if the range has no gaps.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the gaps, in the order they were recorded.
+    pub fn iter(&self) -> AddressGapIter<'t> {
+        AddressGapIter { chunks: self.0.chunks(4) }
+    }
+}
+
+/// An iterator over `AddressGaps`.
+#[derive(Debug,Clone)]
+pub struct AddressGapIter<'t> {
+    chunks: slice::Chunks<'t, u8>,
+}
+
+impl<'t> Iterator for AddressGapIter<'t> {
+    type Item = AddressGap;
+
+    fn next(&mut self) -> Option<AddressGap> {
+        // `AddressGaps` only holds whole gaps
+        self.chunks.next().map(|gap| AddressGap {
+            offset: u16::from(gap[0]) | u16::from(gap[1]) << 8,
+            len: u16::from(gap[2]) | u16::from(gap[3]) << 8,
+        })
+    }
+}
+
+/// The information parsed from a symbol record with kind `S_DEFRANGE`.
+///
+/// The variable's location is computed by a program, which this crate cannot evaluate.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct DefRangeSymbol<'t> {
+    pub program: u32,
+    pub range: AddressRange,
+    pub gaps: AddressGaps<'t>,
+}
+
+/// The information parsed from a symbol record with kind `S_DEFRANGE_REGISTER`.
+///
+/// The variable lives in `register`.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct DefRangeRegisterSymbol<'t> {
+    pub register: Register,
+    /// The variable may not have a user-visible name in this range.
+    pub may_have_no_user_name: bool,
+    pub range: AddressRange,
+    pub gaps: AddressGaps<'t>,
+}
+
+/// The information parsed from a symbol record with kind `S_DEFRANGE_FRAMEPOINTER_REL`.
+///
+/// The variable lives in memory, at `offset` from the frame pointer.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct DefRangeFramePointerRelativeSymbol<'t> {
+    pub offset: i32,
+    pub range: AddressRange,
+    pub gaps: AddressGaps<'t>,
+}
+
+/// The information parsed from a symbol record with kind `S_DEFRANGE_SUBFIELD_REGISTER`.
+///
+/// The field at `parent_offset` in the variable lives in `register`.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct DefRangeSubfieldRegisterSymbol<'t> {
+    pub register: Register,
+    /// The variable may not have a user-visible name in this range.
+    pub may_have_no_user_name: bool,
+    pub parent_offset: u32,
+    pub range: AddressRange,
+    pub gaps: AddressGaps<'t>,
+}
+
+/// The information parsed from a symbol record with kind
+/// `S_DEFRANGE_FRAMEPOINTER_REL_FULL_SCOPE`.
+///
+/// The variable lives in memory, at `offset` from the frame pointer, for the entire scope which
+/// contains it.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct DefRangeFramePointerRelativeFullScopeSymbol {
+    pub offset: i32,
+}
+
+/// The information parsed from a symbol record with kind `S_DEFRANGE_REGISTER_REL`.
+///
+/// The variable lives in memory, at `offset` from the address in `register`.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct DefRangeRegisterRelativeSymbol<'t> {
+    pub register: Register,
+    /// Only the field at `parent_offset` in the variable lives at this location.
+    pub spilled_udt_member: bool,
+    pub parent_offset: u16,
+    pub offset: i32,
+    pub range: AddressRange,
+    pub gaps: AddressGaps<'t>,
+}
+
+/// The information parsed from a symbol record with kind `S_INLINESITE` or `S_INLINESITE2`.
+///
+/// An inline site opens a scope, which is closed by the `S_INLINESITE_END` symbol at `end`. The
+/// symbols within it describe the inlined function's variables and further inline sites.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct InlineSiteSymbol<'t> {
+    /// The enclosing procedure or inline site.
+    pub parent: u32,
+    /// The `S_INLINESITE_END` symbol closing this inline site's scope.
+    pub end: u32,
+    /// The `LF_FUNC_ID` or `LF_MFUNC_ID` record of the inlined function.
+    pub inlinee: ItemIndex,
+    /// The number of times the inlined function was invoked, for `S_INLINESITE2` only.
+    pub invocations: Option<u32>,
+    pub annotations: BinaryAnnotations<'t>,
+}
+
+impl<'t> InlineSiteSymbol<'t> {
+    /// Returns an iterator over the ranges of code of this inline site, and the lines of the
+    /// inlined function they belong to.
+    ///
+    /// The binary annotations only record changes, so this needs the `ProcedureSymbol` which
+    /// contains the inline site to locate its code, and the `InlineeLine` of the inlined function
+    /// from `ModuleInfo::inlinee_lines()` to locate its source.
+    pub fn lines(&self, procedure: &ProcedureSymbol, inlinee: &InlineeLine) -> InlineSiteLineIter<'t> {
+        InlineSiteLineIter::new(self.annotations.iter(), procedure, inlinee)
+    }
+}
+
+/// The information parsed from a symbol record with kind `S_OBJNAME` or `S_OBJNAME_ST`.
+///
+/// The symbol's name is the path of the object file the module was built from.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct ObjNameSymbol {
+    pub signature: u32,
+}
+
+/// The information parsed from a symbol record with kind `S_COMPILE2`, `S_COMPILE2_ST`, or
+/// `S_COMPILE3`.
+///
+/// The symbol's name is the name of the compiler, like `Microsoft (R) Optimizing Compiler`.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct CompileFlagsSymbol {
+    pub language: SourceLanguage,
+    pub flags: CompileFlags,
+    pub cpu_type: CPUType,
+    pub frontend_version: CompilerVersion,
+    pub backend_version: CompilerVersion,
+}
+
+/// The flags of a `CompileFlagsSymbol`.
+#[derive(Debug,Copy,Clone,Default,Eq,PartialEq)]
+pub struct CompileFlags {
+    /// Compiled for edit and continue.
+    pub edit_and_continue: bool,
+    /// Compiled without debugging information.
+    pub no_debug_info: bool,
+    /// Compiled with link time code generation.
+    pub link_time_codegen: bool,
+    /// Compiled with `/bzalign`.
+    pub no_data_align: bool,
+    /// Contains managed code.
+    pub managed: bool,
+    /// Compiled with `/GS`.
+    pub security_checks: bool,
+    /// Compiled with `/hotpatch`.
+    pub hot_patch: bool,
+    /// Converted from CIL with CVTCIL.
+    pub cvtcil: bool,
+    /// Is a MSIL netmodule.
+    pub msil_module: bool,
+    /// Compiled with `/sdl`. Only recorded by `S_COMPILE3`.
+    pub sdl: bool,
+    /// Compiled with profile guided optimization. Only recorded by `S_COMPILE3`.
+    pub pgo: bool,
+    /// Is a `.exp` module. Only recorded by `S_COMPILE3`.
+    pub exp_module: bool,
+}
+
+impl From<u32> for CompileFlags {
+    fn from(flags: u32) -> Self {
+        CompileFlags {
+            edit_and_continue: flags & CV_COMPILE_EC != 0,
+            no_debug_info: flags & CV_COMPILE_NODBGINFO != 0,
+            link_time_codegen: flags & CV_COMPILE_LTCG != 0,
+            no_data_align: flags & CV_COMPILE_NODATAALIGN != 0,
+            managed: flags & CV_COMPILE_MANAGEDPRESENT != 0,
+            security_checks: flags & CV_COMPILE_SECURITYCHECKS != 0,
+            hot_patch: flags & CV_COMPILE_HOTPATCH != 0,
+            cvtcil: flags & CV_COMPILE_CVTCIL != 0,
+            msil_module: flags & CV_COMPILE_MSILMODULE != 0,
+            sdl: flags & CV_COMPILE_SDL != 0,
+            pgo: flags & CV_COMPILE_PGO != 0,
+            exp_module: flags & CV_COMPILE_EXP != 0,
+        }
+    }
+}
+
+/// The version of a compiler's frontend or backend.
+#[derive(Debug,Copy,Clone,Eq,PartialEq,Ord,PartialOrd)]
+pub struct CompilerVersion {
+    pub major: u16,
+    pub minor: u16,
+    pub build: u16,
+    /// The quick fix engineering number, which is always `0` for `S_COMPILE2`.
+    pub qfe: u16,
+}
+
+impl fmt::Display for CompilerVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}.{}", self.major, self.minor, self.build, self.qfe)
+    }
+}
+
+/// The source language of a module.
+///
+/// Named `CV_CFL_LANG` in `cvconst.h`.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub enum SourceLanguage {
+    C,
+    Cpp,
+    Fortran,
+    Masm,
+    Pascal,
+    Basic,
+    Cobol,
+    Link,
+    Cvtres,
+    Cvtpgd,
+    CSharp,
+    VisualBasic,
+    ILAsm,
+    Java,
+    JScript,
+    MSIL,
+    HLSL,
+    OtherValue(u8),
+}
+
+impl From<u8> for SourceLanguage {
+    fn from(v: u8) -> Self {
+        match v {
+            0x00 => SourceLanguage::C,
+            0x01 => SourceLanguage::Cpp,
+            0x02 => SourceLanguage::Fortran,
+            0x03 => SourceLanguage::Masm,
+            0x04 => SourceLanguage::Pascal,
+            0x05 => SourceLanguage::Basic,
+            0x06 => SourceLanguage::Cobol,
+            0x07 => SourceLanguage::Link,
+            0x08 => SourceLanguage::Cvtres,
+            0x09 => SourceLanguage::Cvtpgd,
+            0x0a => SourceLanguage::CSharp,
+            0x0b => SourceLanguage::VisualBasic,
+            0x0c => SourceLanguage::ILAsm,
+            0x0d => SourceLanguage::Java,
+            0x0e => SourceLanguage::JScript,
+            0x0f => SourceLanguage::MSIL,
+            0x10 => SourceLanguage::HLSL,
+            _ => SourceLanguage::OtherValue(v),
+        }
+    }
+}
+
+/// The CPU a module was compiled for.
+///
+/// Named `CV_CPU_TYPE_e` in `cvconst.h`.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub enum CPUType {
+    Intel8080,
+    Intel8086,
+    Intel80286,
+    Intel80386,
+    Intel80486,
+    Pentium,
+    PentiumPro,
+    Pentium3,
+    MIPS,
+    Alpha,
+    PowerPC601,
+    SH3,
+    ARM3,
+    ARM4,
+    ARM4T,
+    ARM5,
+    ARM5T,
+    ARM6,
+    ARM7,
+    IA64,
+    IA64_2,
+    CEE,
+    X64,
+    EBC,
+    Thumb,
+    ARMNT,
+    ARM64,
+    D3D11Shader,
+    OtherValue(u16),
+}
+
+impl From<u16> for CPUType {
+    fn from(v: u16) -> Self {
+        match v {
+            0x00 => CPUType::Intel8080,
+            0x01 => CPUType::Intel8086,
+            0x02 => CPUType::Intel80286,
+            0x03 => CPUType::Intel80386,
+            0x04 => CPUType::Intel80486,
+            0x05 => CPUType::Pentium,
+            0x06 => CPUType::PentiumPro,
+            0x07 => CPUType::Pentium3,
+            0x10 => CPUType::MIPS,
+            0x30 => CPUType::Alpha,
+            0x40 => CPUType::PowerPC601,
+            0x50 => CPUType::SH3,
+            0x60 => CPUType::ARM3,
+            0x61 => CPUType::ARM4,
+            0x62 => CPUType::ARM4T,
+            0x63 => CPUType::ARM5,
+            0x64 => CPUType::ARM5T,
+            0x65 => CPUType::ARM6,
+            0x68 => CPUType::ARM7,
+            0x80 => CPUType::IA64,
+            0x81 => CPUType::IA64_2,
+            0x90 => CPUType::CEE,
+            0xd0 => CPUType::X64,
+            0xe0 => CPUType::EBC,
+            0xf0 => CPUType::Thumb,
+            0xf4 => CPUType::ARMNT,
+            0xf6 => CPUType::ARM64,
+            0x100 => CPUType::D3D11Shader,
+            _ => CPUType::OtherValue(v),
+        }
+    }
+}
+
+/// The information parsed from a symbol record with kind `S_ENVBLOCK`.
+///
+/// This records the environment of the tool which produced the module, such as the working
+/// directory (`cwd`), the tool itself (`exe`), and its command line (`cmd`).
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct EnvBlockSymbol<'t> {
+    data: &'t [u8],
+}
+
+impl<'t> EnvBlockSymbol<'t> {
+    /// Returns an iterator over the key and value pairs, in the order they were recorded.
+    pub fn entries(&self) -> EnvBlockIter<'t> {
+        EnvBlockIter { buf: ParseBuffer::from(self.data) }
+    }
+}
+
+/// An iterator over the entries of an `EnvBlockSymbol`.
+#[derive(Debug)]
+pub struct EnvBlockIter<'t> {
+    buf: ParseBuffer<'t>,
+}
+
+impl<'t> FallibleIterator for EnvBlockIter<'t> {
+    type Item = (RawString<'t>, RawString<'t>);
+    type Error = Error;
+
+    fn next(&mut self) -> result::Result<Option<Self::Item>, Self::Error> {
+        // the list ends with an empty string, or the end of the record
+        if self.buf.len() == 0 {
+            return Ok(None);
+        }
+
+        let key = self.buf.parse_cstring()?;
+        if key.as_bytes().is_empty() {
+            self.buf = ParseBuffer::from(&[][..]);
+            return Ok(None);
+        }
+
+        let value = self.buf.parse_cstring()?;
+        Ok(Some((key, value)))
+    }
+}
+
+/// The information parsed from a symbol record with kind `S_BUILDINFO`.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct BuildInfoSymbol {
+    /// The `LF_BUILDINFO` record describing how the module was built.
+    pub id: ItemIndex,
+}
+
+/// The information parsed from a symbol record with kind `S_FRAMEPROC`.
+///
+/// This describes the stack frame of the enclosing procedure, which can be found with
+/// `ModuleInfo::frame_procedure()`.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct FrameProcedureSymbol {
+    /// The size of the whole stack frame in bytes.
+    pub frame_size: u32,
+    /// The size of the padding in the frame.
+    pub padding_size: u32,
+    /// The offset of the padding, relative to the frame pointer.
+    pub padding_offset: u32,
+    /// The size of the area holding callee saved registers.
+    pub saved_registers_size: u32,
+    /// The offset of the exception handler.
+    pub exception_handler_offset: u32,
+    /// The section of the exception handler.
+    pub exception_handler_section: u16,
+    pub flags: FrameProcedureFlags,
+}
+
+impl FrameProcedureSymbol {
+    /// Returns the register which local variables are addressed relative to, i.e. the frame
+    /// pointer of `S_DEFRANGE_FRAMEPOINTER_REL` symbols, if it can be determined for `cpu_type`.
+    ///
+    /// 32-bit x86 may address locals relative to a virtual frame pointer, which is not a register
+    /// and results in `None`.
+    pub fn local_base_register(&self, cpu_type: CPUType) -> Option<Register> {
+        decode_frame_register(self.flags.local_base_pointer, cpu_type)
+    }
+
+    /// Returns the register which parameters are addressed relative to, if it can be determined
+    /// for `cpu_type`.
+    pub fn param_base_register(&self, cpu_type: CPUType) -> Option<Register> {
+        decode_frame_register(self.flags.param_base_pointer, cpu_type)
+    }
+}
+
+/// Decodes the encoded base pointer of a `FrameProcedureFlags`.
+fn decode_frame_register(encoded: u8, cpu_type: CPUType) -> Option<Register> {
+    let register = match (cpu_type, encoded) {
+        (_, 0) => return None,
+
+        (CPUType::Intel80386, 2) | (CPUType::Intel80486, 2) | (CPUType::Pentium, 2) |
+        (CPUType::PentiumPro, 2) | (CPUType::Pentium3, 2) => X86Register::EBP as u16,
+        (CPUType::Intel80386, 3) | (CPUType::Intel80486, 3) | (CPUType::Pentium, 3) |
+        (CPUType::PentiumPro, 3) | (CPUType::Pentium3, 3) => X86Register::EBX as u16,
+
+        (CPUType::X64, 1) => AMD64Register::RSP as u16,
+        (CPUType::X64, 2) => AMD64Register::RBP as u16,
+        (CPUType::X64, 3) => AMD64Register::R13 as u16,
+
+        (CPUType::ARM64, 1) => ARM64Register::SP as u16,
+        (CPUType::ARM64, 2) => ARM64Register::FP as u16,
+        (CPUType::ARM64, 3) => ARM64Register::X19 as u16,
+
+        _ => return None,
+    };
+
+    Some(Register(register))
+}
+
+/// The flags of a `FrameProcedureSymbol`.
+#[derive(Debug,Copy,Clone,Default,Eq,PartialEq)]
+pub struct FrameProcedureFlags {
+    /// The procedure uses `_alloca()`.
+    pub has_alloca: bool,
+    /// The procedure uses `setjmp()`.
+    pub has_setjmp: bool,
+    /// The procedure uses `longjmp()`.
+    pub has_longjmp: bool,
+    /// The procedure uses inline assembly.
+    pub has_inline_asm: bool,
+    /// The procedure has C++ exception handling states.
+    pub has_eh: bool,
+    /// The procedure was declared `inline`.
+    pub inline_spec: bool,
+    /// The procedure has structured exception handling.
+    pub has_seh: bool,
+    /// The procedure is `__declspec(naked)`.
+    pub naked: bool,
+    /// The procedure has buffer security checks from `/GS`.
+    pub security_checks: bool,
+    /// The procedure was compiled with `/EHa`.
+    pub async_eh: bool,
+    /// The procedure has `/GS` buffer checks, but its stack could not be reordered.
+    pub gs_no_stack_ordering: bool,
+    /// The procedure was inlined into another procedure.
+    pub was_inlined: bool,
+    /// The procedure is `__declspec(strict_gs_check)`.
+    pub gs_check: bool,
+    /// The procedure is `__declspec(safebuffers)`.
+    pub safe_buffers: bool,
+    /// The encoded register local variables are addressed relative to. Decode it with
+    /// `FrameProcedureSymbol::local_base_register()`.
+    pub local_base_pointer: u8,
+    /// The encoded register parameters are addressed relative to. Decode it with
+    /// `FrameProcedureSymbol::param_base_register()`.
+    pub param_base_pointer: u8,
+    /// The procedure was compiled with profile guided optimization.
+    pub pogo_on: bool,
+    /// The profile guided optimization counts are valid.
+    pub valid_counts: bool,
+    /// The procedure was optimized for speed.
+    pub opt_speed: bool,
+    /// The procedure contains Control Flow Guard checks.
+    pub guard_cf: bool,
+    /// The procedure contains Control Flow Guard write checks or instrumentation.
+    pub guard_cfw: bool,
+}
+
+impl From<u32> for FrameProcedureFlags {
+    fn from(flags: u32) -> Self {
+        FrameProcedureFlags {
+            has_alloca: flags & CV_FPFLAG_HASALLOCA != 0,
+            has_setjmp: flags & CV_FPFLAG_HASSETJMP != 0,
+            has_longjmp: flags & CV_FPFLAG_HASLONGJMP != 0,
+            has_inline_asm: flags & CV_FPFLAG_HASINLASM != 0,
+            has_eh: flags & CV_FPFLAG_HASEH != 0,
+            inline_spec: flags & CV_FPFLAG_INLSPEC != 0,
+            has_seh: flags & CV_FPFLAG_HASSEH != 0,
+            naked: flags & CV_FPFLAG_NAKED != 0,
+            security_checks: flags & CV_FPFLAG_SECURITYCHECKS != 0,
+            async_eh: flags & CV_FPFLAG_ASYNCEH != 0,
+            gs_no_stack_ordering: flags & CV_FPFLAG_GSNOSTACKORDERING != 0,
+            was_inlined: flags & CV_FPFLAG_WASINLINED != 0,
+            gs_check: flags & CV_FPFLAG_GSCHECK != 0,
+            safe_buffers: flags & CV_FPFLAG_SAFEBUFFERS != 0,
+            local_base_pointer: (flags >> CV_FPFLAG_LOCALBASEPOINTER_SHIFT) as u8 & 0x3,
+            param_base_pointer: (flags >> CV_FPFLAG_PARAMBASEPOINTER_SHIFT) as u8 & 0x3,
+            pogo_on: flags & CV_FPFLAG_POGOON != 0,
+            valid_counts: flags & CV_FPFLAG_VALIDCOUNTS != 0,
+            opt_speed: flags & CV_FPFLAG_OPTSPEED != 0,
+            guard_cf: flags & CV_FPFLAG_GUARDCF != 0,
+            guard_cfw: flags & CV_FPFLAG_GUARDCFW != 0,
+        }
+    }
+}
+
+/// The information parsed from a symbol record with kind `S_THUNK32` or `S_THUNK32_ST`.
+///
+/// A thunk opens a scope, which is closed by the `S_END` symbol at `end`. The symbol's name is
+/// the name of the thunk, like the name of the imported function for an import thunk.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct ThunkSymbol<'t> {
+    pub parent: u32,
+    pub end: u32,
+    pub next: u32,
+    pub offset: u32,
+    pub segment: u16,
+    /// The length of the thunk's code in bytes.
+    pub len: u16,
+    pub kind: ThunkKind<'t>,
+}
+
+/// The kind of a `ThunkSymbol`, along with the data specific to that kind.
+///
+/// Named `THUNK_ORDINAL` in `cvinfo.h`.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub enum ThunkKind<'t> {
+    /// A standard thunk, like an import thunk.
+    NoType,
+    /// A thunk which adjusts `this` by `delta` before calling the virtual function `target`.
+    Adjustor { delta: i16, target: RawString<'t> },
+    /// A thunk which calls a function through the virtual function table entry at
+    /// `vtable_offset`.
+    VCall { vtable_offset: i16 },
+    /// A thunk which calls the p-code at `segment:offset`.
+    PCode { offset: u32, segment: u16 },
+    /// A thunk which loads the address of a delay loaded function.
+    Load,
+    /// An incremental linking trampoline.
+    TrampolineIncremental,
+    /// A branch island trampoline.
+    TrampolineBranchIsland,
+    OtherValue(u8),
+}
+
+/// The information parsed from a symbol record with kind `S_TRAMPOLINE`.
+///
+/// A trampoline is a small piece of code generated by the linker, which jumps to `target`. The
+/// symbol has no name; the name of the trampoline is the name of its target.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct TrampolineSymbol {
+    pub kind: TrampolineKind,
+    /// The length of the trampoline's code in bytes.
+    pub size: u16,
+    pub thunk_offset: u32,
+    pub target_offset: u32,
+    pub thunk_segment: u16,
+    pub target_segment: u16,
+}
+
+/// The kind of a `TrampolineSymbol`.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub enum TrampolineKind {
+    /// A trampoline which allows a function to be relocated by incremental linking.
+    Incremental,
+    /// A trampoline which extends the range of a branch.
+    BranchIsland,
+    OtherValue(u16),
+}
+
+impl From<u16> for TrampolineKind {
+    fn from(v: u16) -> Self {
+        match v {
+            0 => TrampolineKind::Incremental,
+            1 => TrampolineKind::BranchIsland,
+            _ => TrampolineKind::OtherValue(v),
+        }
+    }
+}
+
+/// The information parsed from a symbol record with kind `S_LABEL32` or `S_LABEL32_ST`.
+///
+/// A label marks a location within a procedure, like a jump target or the start of a jump table.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct LabelSymbol {
+    pub offset: u32,
+    pub segment: u16,
+    pub flags: ProcedureFlags,
+}
+
+/// The information parsed from a symbol record with kind `S_BLOCK32` or `S_BLOCK32_ST`.
+///
+/// A block opens a scope within a procedure, which is closed by the `S_END` symbol at `end`.
+#[derive(Debug,Copy,Clone,Eq,PartialEq)]
+pub struct BlockSymbol {
+    /// The enclosing procedure or block.
+    pub parent: u32,
+    pub end: u32,
+    /// The length of the block's code in bytes.
+    pub len: u32,
+    pub offset: u32,
+    pub segment: u16,
+}
+
 /// A `SymbolIter` iterates over a `SymbolTable`, producing `Symbol`s.
 ///
 /// Symbol tables are represented internally as a series of records, each of which have a length, a
@@ -420,7 +1699,7 @@ mod tests {
         use common::*;
         use symbol::*;
 
-        fn parse<'s>(buf: &'s [u8]) -> Result<(Symbol<'s>,SymbolData,String)> {
+        fn parse<'s>(buf: &'s [u8]) -> Result<(Symbol<'s>,SymbolData<'s>,String)> {
             let symbol = Symbol(buf);
 
             let data = symbol.parse()?;
@@ -491,5 +1770,216 @@ mod tests {
             assert_eq!(data, SymbolData::ProcedureReference(ProcedureReferenceSymbol { global: false, sum_name: 0, symbol_index: 1152, module: 182 }));
             assert_eq!(name, "capture_current_context");
         }
+
+        #[test]
+        fn kind_1110() {
+            let buf = &[16, 17, 0, 0, 0, 0, 216, 0, 0, 0, 0, 0, 0, 0, 14, 0, 0, 0, 5, 0, 0, 0, 13, 0, 0, 0, 3, 16, 0, 0, 240, 84, 0, 0, 1, 0, 0, 66, 97, 122, 58, 58, 102, 95, 112, 117, 98, 108, 105, 99, 0, 0, 0, 0];
+            let (symbol, data, name) = parse(buf).expect("parse");
+            assert_eq!(symbol.raw_kind(), 0x1110);
+            assert_eq!(data, SymbolData::Procedure(ProcedureSymbol { global: true, dpc: false, parent: 0, end: 216, next: 0, len: 14, dbg_start_offset: 5, dbg_end_offset: 13, type_index: 4099, offset: 21744, segment: 1, flags: ProcedureFlags::default() }));
+            assert_eq!(name, "Baz::f_public");
+        }
+
+        #[test]
+        fn kind_110f() {
+            let buf = &[15, 17, 0, 0, 0, 0, 40, 4, 0, 0, 0, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 7, 0, 0, 0, 113, 16, 0, 0, 192, 85, 0, 0, 1, 0, 64, 95, 95, 108, 111, 99, 97, 108, 95, 115, 116, 100, 105, 111, 95, 112, 114, 105, 110, 116, 102, 95, 111, 112, 116, 105, 111, 110, 115, 0];
+            let (symbol, data, name) = parse(buf).expect("parse");
+            assert_eq!(symbol.raw_kind(), 0x110f);
+            assert_eq!(data, SymbolData::Procedure(ProcedureSymbol { global: false, dpc: false, parent: 0, end: 1064, next: 0, len: 8, dbg_start_offset: 0, dbg_end_offset: 7, type_index: 4209, offset: 21952, segment: 1, flags: ProcedureFlags { noinline: true, .. ProcedureFlags::default() } }));
+            assert_eq!(name, "__local_stdio_printf_options");
+        }
+
+        #[test]
+        fn kind_1111() {
+            let buf = &[17, 17, 8, 0, 0, 0, 1, 16, 0, 0, 79, 1, 116, 104, 105, 115, 0, 0];
+            let (symbol, data, name) = parse(buf).expect("parse");
+            assert_eq!(symbol.raw_kind(), 0x1111);
+            assert_eq!(data, SymbolData::RegisterRelative(RegisterRelativeSymbol { offset: 8, type_index: 4097, register: Register(335) }));
+            assert_eq!(name, "this");
+        }
+
+        #[test]
+        fn kind_113e() {
+            let buf = &[62, 17, 48, 0, 0, 0, 0, 0, 104, 97, 115, 95, 99, 99, 116, 111, 114, 0];
+            let (symbol, data, name) = parse(buf).expect("parse");
+            assert_eq!(symbol.raw_kind(), 0x113e);
+            assert_eq!(data, SymbolData::Local(LocalSymbol { type_index: 48, flags: LocalVariableFlags::default() }));
+            assert_eq!(name, "has_cctor");
+        }
+
+        #[test]
+        fn kind_1141() {
+            let buf = &[65, 17, 68, 1, 0, 0, 7, 88, 0, 0, 1, 0, 119, 0, 5, 0, 109, 0];
+            let (symbol, data, name) = parse(buf).expect("parse");
+            assert_eq!(symbol.raw_kind(), 0x1141);
+            assert_eq!(data, SymbolData::DefRangeRegister(DefRangeRegisterSymbol { register: Register(324), may_have_no_user_name: false, range: AddressRange { offset: 22535, segment: 1, len: 119 }, gaps: AddressGaps(&[5, 0, 109, 0]) }));
+
+            let gaps: Vec<AddressGap> = match data {
+                SymbolData::DefRangeRegister(data) => data.gaps.iter().collect(),
+                other => panic!("unexpected {:?}", other),
+            };
+            assert_eq!(gaps, vec![AddressGap { offset: 5, len: 109 }]);
+            assert_eq!(name, "");
+        }
+
+        #[test]
+        fn kind_1143() {
+            let buf = &[67, 17, 107, 1, 0, 0, 4, 0, 0, 0, 146, 101, 0, 0, 1, 0, 14, 0];
+            let (symbol, data, name) = parse(buf).expect("parse");
+            assert_eq!(symbol.raw_kind(), 0x1143);
+            assert_eq!(data, SymbolData::DefRangeSubfieldRegister(DefRangeSubfieldRegisterSymbol { register: Register(363), may_have_no_user_name: false, parent_offset: 4, range: AddressRange { offset: 26002, segment: 1, len: 14 }, gaps: AddressGaps(&[]) }));
+            assert_eq!(name, "");
+        }
+
+        #[test]
+        fn kind_1144() {
+            let buf = &[68, 17, 32, 0, 0, 0];
+            let (symbol, data, name) = parse(buf).expect("parse");
+            assert_eq!(symbol.raw_kind(), 0x1144);
+            assert_eq!(data, SymbolData::DefRangeFramePointerRelativeFullScope(DefRangeFramePointerRelativeFullScopeSymbol { offset: 32 }));
+            assert_eq!(name, "");
+        }
+
+        #[test]
+        fn kind_1145() {
+            let buf = &[69, 17, 79, 1, 0, 0, 40, 0, 0, 0, 36, 92, 0, 0, 1, 0, 1, 0];
+            let (symbol, data, name) = parse(buf).expect("parse");
+            assert_eq!(symbol.raw_kind(), 0x1145);
+            assert_eq!(data, SymbolData::DefRangeRegisterRelative(DefRangeRegisterRelativeSymbol { register: Register(335), spilled_udt_member: false, parent_offset: 0, offset: 40, range: AddressRange { offset: 23588, segment: 1, len: 1 }, gaps: AddressGaps(&[]) }));
+            assert_eq!(name, "");
+        }
+
+        #[test]
+        fn kind_114d() {
+            let buf = &[77, 17, 40, 9, 0, 0, 132, 9, 0, 0, 97, 16, 0, 0, 12, 5, 4, 6, 4, 12, 5, 9];
+            let (symbol, data, name) = parse(buf).expect("parse");
+            assert_eq!(symbol.raw_kind(), 0x114d);
+            assert_eq!(data, SymbolData::InlineSite(InlineSiteSymbol { parent: 2344, end: 2436, inlinee: 4193, invocations: None, annotations: BinaryAnnotations::new(&[12, 5, 4, 6, 4, 12, 5, 9]) }));
+            assert_eq!(name, "");
+
+            let annotations: Vec<BinaryAnnotation> = match data {
+                SymbolData::InlineSite(site) => site.annotations.iter().collect().expect("annotations"),
+                other => panic!("unexpected {:?}", other),
+            };
+            assert_eq!(annotations, vec![BinaryAnnotation::ChangeCodeLengthAndCodeOffset(5, 4), BinaryAnnotation::ChangeLineOffset(2), BinaryAnnotation::ChangeCodeLengthAndCodeOffset(5, 9)]);
+        }
+
+        #[test]
+        fn kind_1101() {
+            let buf = &[1, 17, 0, 0, 0, 0, 42, 32, 76, 105, 110, 107, 101, 114, 32, 42, 0, 0];
+            let (symbol, data, name) = parse(buf).expect("parse");
+            assert_eq!(symbol.raw_kind(), 0x1101);
+            assert_eq!(data, SymbolData::ObjName(ObjNameSymbol { signature: 0 }));
+            assert_eq!(name, "* Linker *");
+        }
+
+        #[test]
+        fn kind_1116() {
+            let buf = &[22, 17, 7, 0, 0, 0, 208, 0, 0, 0, 0, 0, 0, 0, 14, 0, 0, 0, 109, 93, 77, 105, 99, 114, 111, 115, 111, 102, 116, 32, 40, 82, 41, 32, 76, 73, 78, 75, 0, 0, 0, 0];
+            let (symbol, data, name) = parse(buf).expect("parse");
+            assert_eq!(symbol.raw_kind(), 0x1116);
+            assert_eq!(data, SymbolData::CompileFlags(CompileFlagsSymbol { language: SourceLanguage::Link, flags: CompileFlags::default(), cpu_type: CPUType::X64, frontend_version: CompilerVersion { major: 0, minor: 0, build: 0, qfe: 0 }, backend_version: CompilerVersion { major: 14, minor: 0, build: 23917, qfe: 0 } }));
+            assert_eq!(name, "Microsoft (R) LINK");
+        }
+
+        #[test]
+        fn kind_113c() {
+            let buf = &[60, 17, 1, 96, 0, 0, 208, 0, 19, 0, 0, 0, 151, 94, 1, 0, 19, 0, 0, 0, 151, 94, 1, 0, 77, 105, 99, 114, 111, 115, 111, 102, 116, 32, 40, 82, 41, 32, 79, 112, 116, 105, 109, 105, 122, 105, 110, 103, 32, 67, 111, 109, 112, 105, 108, 101, 114, 0];
+            let (symbol, data, name) = parse(buf).expect("parse");
+            assert_eq!(symbol.raw_kind(), 0x113c);
+            assert_eq!(data, SymbolData::CompileFlags(CompileFlagsSymbol { language: SourceLanguage::Cpp, flags: CompileFlags { security_checks: true, hot_patch: true, .. CompileFlags::default() }, cpu_type: CPUType::X64, frontend_version: CompilerVersion { major: 19, minor: 0, build: 24215, qfe: 1 }, backend_version: CompilerVersion { major: 19, minor: 0, build: 24215, qfe: 1 } }));
+            assert_eq!(name, "Microsoft (R) Optimizing Compiler");
+        }
+
+        #[test]
+        fn kind_113d() {
+            let buf = &[61, 17, 0, 99, 119, 100, 0, 99, 58, 92, 0, 99, 109, 100, 0, 32, 47, 100, 101, 98, 117, 103, 0, 0, 0, 0];
+            let (symbol, data, name) = parse(buf).expect("parse");
+            assert_eq!(symbol.raw_kind(), 0x113d);
+            assert_eq!(name, "");
+
+            let entries: Vec<(RawString, RawString)> = match data {
+                SymbolData::EnvBlock(block) => block.entries().collect().expect("entries"),
+                other => panic!("unexpected {:?}", other),
+            };
+            assert_eq!(entries, vec![("cwd".into(), "c:\\".into()), ("cmd".into(), " /debug".into())]);
+        }
+
+        #[test]
+        fn kind_114c() {
+            let buf = &[76, 17, 59, 16, 0, 0];
+            let (symbol, data, name) = parse(buf).expect("parse");
+            assert_eq!(symbol.raw_kind(), 0x114c);
+            assert_eq!(data, SymbolData::BuildInfo(BuildInfoSymbol { id: 4155 }));
+            assert_eq!(name, "");
+        }
+
+        #[test]
+        fn kind_1012() {
+            let buf = &[18, 16, 40, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 66, 17, 0, 0, 0];
+            let (symbol, data, name) = parse(buf).expect("parse");
+            assert_eq!(symbol.raw_kind(), 0x1012);
+            assert_eq!(data, SymbolData::FrameProcedure(FrameProcedureSymbol { frame_size: 40, padding_size: 0, padding_offset: 0, saved_registers_size: 0, exception_handler_offset: 0, exception_handler_section: 0, flags: FrameProcedureFlags { async_eh: true, local_base_pointer: 1, param_base_pointer: 1, opt_speed: true, .. FrameProcedureFlags::default() } }));
+            assert_eq!(name, "");
+
+            let frame = match data { SymbolData::FrameProcedure(frame) => frame, _ => unreachable!() };
+            assert_eq!(frame.local_base_register(CPUType::X64), Some(Register(335)));
+            assert_eq!(frame.param_base_register(CPUType::ARM64), Some(Register(81)));
+            assert_eq!(frame.local_base_register(CPUType::Pentium3), None);
+        }
+
+        #[test]
+        fn kind_1102() {
+            let buf = &[2, 17, 0, 0, 0, 0, 112, 0, 0, 0, 0, 0, 0, 0, 48, 125, 5, 0, 1, 0, 6, 0, 0, 87, 114, 105, 116, 101, 70, 105, 108, 101, 0, 0];
+            let (symbol, data, name) = parse(buf).expect("parse");
+            assert_eq!(symbol.raw_kind(), 0x1102);
+            assert_eq!(data, SymbolData::Thunk(ThunkSymbol { parent: 0, end: 112, next: 0, offset: 359728, segment: 1, len: 6, kind: ThunkKind::NoType }));
+            assert_eq!(name, "WriteFile");
+        }
+
+        #[test]
+        fn kind_1102_adjustor() {
+            let buf = &[2, 17, 0, 0, 0, 0, 64, 0, 0, 0, 0, 0, 0, 0, 0, 16, 0, 0, 1, 0, 5, 0, 1, 116, 104, 117, 110, 107, 0, 248, 255, 70, 111, 111, 0];
+            let (symbol, data, name) = parse(buf).expect("parse");
+            assert_eq!(symbol.raw_kind(), 0x1102);
+            assert_eq!(data, SymbolData::Thunk(ThunkSymbol { parent: 0, end: 64, next: 0, offset: 4096, segment: 1, len: 5, kind: ThunkKind::Adjustor { delta: -8, target: RawString::from("Foo") } }));
+            assert_eq!(name, "thunk");
+        }
+
+        #[test]
+        fn kind_1102_vcall() {
+            let buf = &[2, 17, 0, 0, 0, 0, 64, 0, 0, 0, 0, 0, 0, 0, 0, 16, 0, 0, 1, 0, 5, 0, 2, 116, 104, 117, 110, 107, 0, 24, 0, 0];
+            let (symbol, data, name) = parse(buf).expect("parse");
+            assert_eq!(symbol.raw_kind(), 0x1102);
+            assert_eq!(data, SymbolData::Thunk(ThunkSymbol { parent: 0, end: 64, next: 0, offset: 4096, segment: 1, len: 5, kind: ThunkKind::VCall { vtable_offset: 24 } }));
+            assert_eq!(name, "thunk");
+        }
+
+        #[test]
+        fn kind_1103() {
+            let buf = &[3, 17, 148, 3, 0, 0, 120, 4, 0, 0, 64, 0, 0, 0, 141, 122, 5, 0, 1, 0, 0, 0];
+            let (symbol, data, name) = parse(buf).expect("parse");
+            assert_eq!(symbol.raw_kind(), 0x1103);
+            assert_eq!(data, SymbolData::Block(BlockSymbol { parent: 916, end: 1144, len: 64, offset: 359053, segment: 1 }));
+            assert_eq!(name, "");
+        }
+
+        #[test]
+        fn kind_1105() {
+            let buf = &[5, 17, 83, 89, 0, 0, 1, 0, 16, 36, 76, 78, 50, 51, 0, 0, 0, 0];
+            let (symbol, data, name) = parse(buf).expect("parse");
+            assert_eq!(symbol.raw_kind(), 0x1105);
+            assert_eq!(data, SymbolData::Label(LabelSymbol { offset: 22867, segment: 1, flags: ProcedureFlags { notreached: true, .. ProcedureFlags::default() } }));
+            assert_eq!(name, "$LN23");
+        }
+
+        #[test]
+        fn kind_112c() {
+            let buf = &[44, 17, 0, 0, 5, 0, 5, 0, 0, 0, 92, 146, 0, 0, 1, 0, 1, 0];
+            let (symbol, data, name) = parse(buf).expect("parse");
+            assert_eq!(symbol.raw_kind(), 0x112c);
+            assert_eq!(data, SymbolData::Trampoline(TrampolineSymbol { kind: TrampolineKind::Incremental, size: 5, thunk_offset: 5, target_offset: 37468, thunk_segment: 1, target_segment: 1 }));
+            assert_eq!(name, "");
+        }
     }
 }
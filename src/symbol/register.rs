@@ -0,0 +1,162 @@
+// Copyright 2017 pdb Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+// CodeView register numbers, from `CV_HREG_e`:
+//   https://github.com/Microsoft/microsoft-pdb/blob/082c5290e5aff028ae84e43affa8be717aa7af73/include/cvconst.h
+
+use std::fmt;
+
+/// A register number, as stored in symbols like `S_REGREL32` and `S_REGISTER`.
+///
+/// The meaning of a register number depends on the CPU the code was compiled for. Use `x86()`,
+/// `amd64()`, or `arm64()` to interpret it for a particular architecture.
+#[derive(Debug,Copy,Clone,Eq,PartialEq,Hash)]
+pub struct Register(pub u16);
+
+impl Register {
+    /// Interprets this register number for 32-bit x86.
+    pub fn x86(self) -> Option<X86Register> {
+        X86Register::from_u16(self.0)
+    }
+
+    /// Interprets this register number for x64.
+    pub fn amd64(self) -> Option<AMD64Register> {
+        AMD64Register::from_u16(self.0)
+    }
+
+    /// Interprets this register number for 64-bit ARM.
+    pub fn arm64(self) -> Option<ARM64Register> {
+        ARM64Register::from_u16(self.0)
+    }
+}
+
+macro_rules! registers {
+    ( $(#[$attr:meta])* enum $name:ident { $( $variant:ident = $value:expr, )* } ) => {
+        $(#[$attr])*
+        #[derive(Debug,Copy,Clone,Eq,PartialEq,Hash)]
+        pub enum $name {
+            $( $variant = $value, )*
+        }
+
+        impl $name {
+            /// Returns the register with the given CodeView register number, if there is one.
+            pub fn from_u16(value: u16) -> Option<Self> {
+                match value {
+                    $( $value => Some($name::$variant), )*
+                    _ => None,
+                }
+            }
+
+            /// Returns the name of the register, like `"EAX"`.
+            pub fn name(self) -> &'static str {
+                match self {
+                    $( $name::$variant => stringify!($variant), )*
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(self.name())
+            }
+        }
+    }
+}
+
+registers! {
+    /// The registers of 32-bit x86, named `CV_REG_*` in `cvconst.h`.
+    enum X86Register {
+        NONE = 0, AL = 1, CL = 2, DL = 3, BL = 4, AH = 5, CH = 6, DH = 7, BH = 8, AX = 9, CX = 10,
+        DX = 11, BX = 12, SP = 13, BP = 14, SI = 15, DI = 16, EAX = 17, ECX = 18, EDX = 19,
+        EBX = 20, ESP = 21, EBP = 22, ESI = 23, EDI = 24, ES = 25, CS = 26, SS = 27, DS = 28,
+        FS = 29, GS = 30, IP = 31, FLAGS = 32, EIP = 33, EFLAGS = 34, CR0 = 80, CR1 = 81, CR2 = 82,
+        CR3 = 83, CR4 = 84, DR0 = 90, DR1 = 91, DR2 = 92, DR3 = 93, DR4 = 94, DR5 = 95, DR6 = 96,
+        DR7 = 97, GDTR = 110, GDTL = 111, IDTR = 112, IDTL = 113, LDTR = 114, TR = 115, ST0 = 128,
+        ST1 = 129, ST2 = 130, ST3 = 131, ST4 = 132, ST5 = 133, ST6 = 134, ST7 = 135, CTRL = 136,
+        STAT = 137, TAG = 138, FPIP = 139, FPCS = 140, FPDO = 141, FPDS = 142, ISEM = 143,
+        FPEIP = 144, FPEDO = 145, MM0 = 146, MM1 = 147, MM2 = 148, MM3 = 149, MM4 = 150, MM5 = 151,
+        MM6 = 152, MM7 = 153, XMM0 = 154, XMM1 = 155, XMM2 = 156, XMM3 = 157, XMM4 = 158,
+        XMM5 = 159, XMM6 = 160, XMM7 = 161, MXCSR = 211,
+    }
+}
+
+registers! {
+    /// The registers of x64, named `CV_AMD64_*` in `cvconst.h`.
+    enum AMD64Register {
+        NONE = 0, AL = 1, CL = 2, DL = 3, BL = 4, AH = 5, CH = 6, DH = 7, BH = 8, AX = 9, CX = 10,
+        DX = 11, BX = 12, SP = 13, BP = 14, SI = 15, DI = 16, EAX = 17, ECX = 18, EDX = 19,
+        EBX = 20, ESP = 21, EBP = 22, ESI = 23, EDI = 24, ES = 25, CS = 26, SS = 27, DS = 28,
+        FS = 29, GS = 30, FLAGS = 32, RIP = 33, EFLAGS = 34, CR0 = 80, CR1 = 81, CR2 = 82,
+        CR3 = 83, CR4 = 84, CR5 = 85, CR6 = 86, CR7 = 87, CR8 = 88, DR0 = 90, DR1 = 91, DR2 = 92,
+        DR3 = 93, DR4 = 94, DR5 = 95, DR6 = 96, DR7 = 97, ST0 = 128, ST1 = 129, ST2 = 130,
+        ST3 = 131, ST4 = 132, ST5 = 133, ST6 = 134, ST7 = 135, CTRL = 136, STAT = 137, TAG = 138,
+        FPIP = 139, FPCS = 140, FPDO = 141, FPDS = 142, ISEM = 143, FPEIP = 144, FPEDO = 145,
+        MM0 = 146, MM1 = 147, MM2 = 148, MM3 = 149, MM4 = 150, MM5 = 151, MM6 = 152, MM7 = 153,
+        XMM0 = 154, XMM1 = 155, XMM2 = 156, XMM3 = 157, XMM4 = 158, XMM5 = 159, XMM6 = 160,
+        XMM7 = 161, MXCSR = 211, XMM8 = 252, XMM9 = 253, XMM10 = 254, XMM11 = 255, XMM12 = 256,
+        XMM13 = 257, XMM14 = 258, XMM15 = 259, SIL = 324, DIL = 325, BPL = 326, SPL = 327,
+        RAX = 328, RBX = 329, RCX = 330, RDX = 331, RSI = 332, RDI = 333, RBP = 334, RSP = 335,
+        R8 = 336, R9 = 337, R10 = 338, R11 = 339, R12 = 340, R13 = 341, R14 = 342, R15 = 343,
+        R8B = 344, R9B = 345, R10B = 346, R11B = 347, R12B = 348, R13B = 349, R14B = 350,
+        R15B = 351, R8W = 352, R9W = 353, R10W = 354, R11W = 355, R12W = 356, R13W = 357,
+        R14W = 358, R15W = 359, R8D = 360, R9D = 361, R10D = 362, R11D = 363, R12D = 364,
+        R13D = 365, R14D = 366, R15D = 367,
+    }
+}
+
+registers! {
+    /// The registers of 64-bit ARM, named `CV_ARM64_*` in `cvconst.h`.
+    enum ARM64Register {
+        NOREG = 0, W0 = 10, W1 = 11, W2 = 12, W3 = 13, W4 = 14, W5 = 15, W6 = 16, W7 = 17, W8 = 18,
+        W9 = 19, W10 = 20, W11 = 21, W12 = 22, W13 = 23, W14 = 24, W15 = 25, W16 = 26, W17 = 27,
+        W18 = 28, W19 = 29, W20 = 30, W21 = 31, W22 = 32, W23 = 33, W24 = 34, W25 = 35, W26 = 36,
+        W27 = 37, W28 = 38, W29 = 39, W30 = 40, WZR = 41, X0 = 50, X1 = 51, X2 = 52, X3 = 53,
+        X4 = 54, X5 = 55, X6 = 56, X7 = 57, X8 = 58, X9 = 59, X10 = 60, X11 = 61, X12 = 62,
+        X13 = 63, X14 = 64, X15 = 65, X16 = 66, X17 = 67, X18 = 68, X19 = 69, X20 = 70, X21 = 71,
+        X22 = 72, X23 = 73, X24 = 74, X25 = 75, X26 = 76, X27 = 77, X28 = 78, FP = 79, LR = 80,
+        SP = 81, ZR = 82, PC = 83, NZCV = 90, CPSR = 91, S0 = 100, S1 = 101, S2 = 102, S3 = 103,
+        S4 = 104, S5 = 105, S6 = 106, S7 = 107, S8 = 108, S9 = 109, S10 = 110, S11 = 111,
+        S12 = 112, S13 = 113, S14 = 114, S15 = 115, S16 = 116, S17 = 117, S18 = 118, S19 = 119,
+        S20 = 120, S21 = 121, S22 = 122, S23 = 123, S24 = 124, S25 = 125, S26 = 126, S27 = 127,
+        S28 = 128, S29 = 129, S30 = 130, S31 = 131, D0 = 140, D1 = 141, D2 = 142, D3 = 143,
+        D4 = 144, D5 = 145, D6 = 146, D7 = 147, D8 = 148, D9 = 149, D10 = 150, D11 = 151,
+        D12 = 152, D13 = 153, D14 = 154, D15 = 155, D16 = 156, D17 = 157, D18 = 158, D19 = 159,
+        D20 = 160, D21 = 161, D22 = 162, D23 = 163, D24 = 164, D25 = 165, D26 = 166, D27 = 167,
+        D28 = 168, D29 = 169, D30 = 170, D31 = 171, Q0 = 180, Q1 = 181, Q2 = 182, Q3 = 183,
+        Q4 = 184, Q5 = 185, Q6 = 186, Q7 = 187, Q8 = 188, Q9 = 189, Q10 = 190, Q11 = 191,
+        Q12 = 192, Q13 = 193, Q14 = 194, Q15 = 195, Q16 = 196, Q17 = 197, Q18 = 198, Q19 = 199,
+        Q20 = 200, Q21 = 201, Q22 = 202, Q23 = 203, Q24 = 204, Q25 = 205, Q26 = 206, Q27 = 207,
+        Q28 = 208, Q29 = 209, Q30 = 210, Q31 = 211, FPSR = 220, FPCR = 221,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod registers {
+        use symbol::register::*;
+
+        #[test]
+        fn test_register() {
+            assert_eq!(Register(17).x86(), Some(X86Register::EAX));
+            assert_eq!(Register(335).amd64(), Some(AMD64Register::RSP));
+            assert_eq!(Register(343).amd64(), Some(AMD64Register::R15));
+            assert_eq!(Register(259).amd64(), Some(AMD64Register::XMM15));
+            assert_eq!(Register(79).arm64(), Some(ARM64Register::FP));
+            assert_eq!(Register(211).arm64(), Some(ARM64Register::Q31));
+
+            assert_eq!(Register(335).x86(), None);
+            assert_eq!(Register(1000).arm64(), None);
+        }
+
+        #[test]
+        fn test_name() {
+            assert_eq!(AMD64Register::RBP.name(), "RBP");
+            assert_eq!(X86Register::XMM0.to_string(), "XMM0");
+            assert_eq!(ARM64Register::W30.to_string(), "W30");
+        }
+    }
+}
@@ -0,0 +1,155 @@
+// Copyright 2017 pdb Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::result;
+
+use common::*;
+use FallibleIterator;
+use super::{Symbol, SymbolIter};
+use super::constants::*;
+
+/// Returns true if symbols of this kind open a scope, which a later symbol closes.
+fn starts_scope(kind: u16) -> bool {
+    is_procedure(kind) || matches!(kind,
+        S_THUNK32 | S_THUNK32_ST |
+        S_BLOCK32 | S_BLOCK32_ST |
+        S_WITH32 | S_WITH32_ST |
+        S_SEPCODE |
+        S_INLINESITE | S_INLINESITE2)
+}
+
+/// Returns true if symbols of this kind open the scope of a procedure.
+fn is_procedure(kind: u16) -> bool {
+    matches!(kind,
+        S_LPROC32 | S_LPROC32_ST | S_GPROC32 | S_GPROC32_ST |
+        S_LPROC32_ID | S_GPROC32_ID | S_LPROC32_DPC | S_LPROC32_DPC_ID |
+        S_LMANPROC | S_LMANPROC_ST | S_GMANPROC | S_GMANPROC_ST)
+}
+
+/// Returns true if symbols of this kind close the innermost open scope.
+fn ends_scope(kind: u16) -> bool {
+    matches!(kind, S_END | S_PROC_ID_END | S_INLINESITE_END)
+}
+
+/// A symbol of a module, along with its position in the module's tree of scopes.
+#[derive(Debug,Copy,Clone,PartialEq)]
+pub struct ScopedSymbol<'t> {
+    /// The symbol itself.
+    pub symbol: Symbol<'t>,
+    /// The byte offset of the symbol in the module info stream.
+    ///
+    /// This is how other symbols refer to it, for example in `ProcedureSymbol::end` or
+    /// `ProcedureReferenceSymbol::symbol_index`.
+    pub offset: u32,
+    /// The number of scopes enclosing the symbol.
+    ///
+    /// A symbol which closes a scope has the same depth as the symbol which opened it.
+    pub depth: usize,
+    /// The offset of the innermost procedure enclosing the symbol, if any.
+    pub procedure: Option<u32>,
+}
+
+/// A `ScopedSymbolIter` iterates over the symbols of a module, tracking the scopes they are in.
+///
+/// Procedures, blocks, thunks, and inline sites open scopes, and `S_END`, `S_PROC_ID_END`, or
+/// `S_INLINESITE_END` close them again. Create one with `ModuleInfo::scoped_symbols()`.
+#[derive(Debug)]
+pub struct ScopedSymbolIter<'t> {
+    symbols: SymbolIter<'t>,
+    base: u32,
+
+    /// The offsets of the symbols which opened the scopes the iterator is in, and whether they
+    /// are procedures.
+    scopes: Vec<(u32, bool)>,
+}
+
+impl<'t> ScopedSymbolIter<'t> {
+    /// Creates an iterator over `buf`, which starts `base` bytes into the module info stream.
+    pub(crate) fn new(buf: ParseBuffer<'t>, base: u32) -> Self {
+        ScopedSymbolIter {
+            symbols: SymbolIter::new(buf),
+            base,
+            scopes: Vec::new(),
+        }
+    }
+
+    fn procedure(&self) -> Option<u32> {
+        self.scopes.iter().rev().find(|&&(_, procedure)| procedure).map(|&(offset, _)| offset)
+    }
+}
+
+impl<'t> FallibleIterator for ScopedSymbolIter<'t> {
+    type Item = ScopedSymbol<'t>;
+    type Error = Error;
+
+    fn next(&mut self) -> result::Result<Option<Self::Item>, Self::Error> {
+        let offset = self.base + self.symbols.buf.pos() as u32;
+        let symbol = match self.symbols.next()? {
+            Some(symbol) => symbol,
+            None => return Ok(None),
+        };
+
+        let kind = symbol.raw_kind();
+        if ends_scope(kind) {
+            // a stray end symbol leaves us at the top level
+            self.scopes.pop();
+        }
+
+        let scoped = ScopedSymbol {
+            symbol,
+            offset,
+            depth: self.scopes.len(),
+            procedure: self.procedure(),
+        };
+
+        if starts_scope(kind) {
+            self.scopes.push((offset, is_procedure(kind)));
+        }
+
+        Ok(Some(scoped))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod scopes {
+        use symbol::scope::*;
+
+        fn record(data: &mut Vec<u8>, kind: u16) {
+            data.extend_from_slice(&[2, 0, kind as u8, (kind >> 8) as u8]);
+        }
+
+        #[test]
+        fn test_scopes() {
+            let mut data = Vec::new();
+            for &kind in &[S_GPROC32, S_BLOCK32, S_INLINESITE, S_INLINESITE_END, S_END, S_END,
+                           S_UDT, S_END, S_THUNK32, S_END] {
+                record(&mut data, kind);
+            }
+
+            let mut iter = ScopedSymbolIter::new(ParseBuffer::from(data.as_slice()), 4);
+            let mut summary = Vec::new();
+            while let Some(scoped) = iter.next().expect("next") {
+                summary.push((scoped.symbol.raw_kind(), scoped.offset, scoped.depth, scoped.procedure));
+            }
+
+            assert_eq!(summary, vec![
+                (S_GPROC32, 4, 0, None),
+                (S_BLOCK32, 8, 1, Some(4)),
+                (S_INLINESITE, 12, 2, Some(4)),
+                (S_INLINESITE_END, 16, 2, Some(4)),
+                (S_END, 20, 1, Some(4)),
+                (S_END, 24, 0, None),
+                (S_UDT, 28, 0, None),
+                // a stray end symbol
+                (S_END, 32, 0, None),
+                (S_THUNK32, 36, 0, None),
+                (S_END, 40, 0, None),
+            ]);
+        }
+    }
+}
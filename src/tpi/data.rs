@@ -51,7 +51,7 @@ impl<'t> TypeData<'t> {
             _ => { return None }
         };
 
-        Some(name.clone())
+        Some(*name)
     }
 }
 
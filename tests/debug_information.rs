@@ -0,0 +1,269 @@
+extern crate pdb;
+use pdb::FallibleIterator;
+
+fn setup<F>(func: F) where F: FnOnce(&pdb::DebugInformation) {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+    let debug_information = pdb.debug_information().expect("debug information");
+
+    func(&debug_information);
+}
+
+#[test]
+fn section_contributions() {
+    setup(|dbi| {
+        let modules: Vec<pdb::Module> = dbi.modules().expect("modules").collect().expect("collect modules");
+
+        let mut count: usize = 0;
+        let mut last: Option<(u16, u32)> = None;
+        let mut iter = dbi.section_contributions().expect("section contributions");
+        while let Some(contribution) = iter.next().expect("next contribution") {
+            // every contribution belongs to a module we know about
+            assert!((contribution.module as usize) < modules.len());
+
+            // contributions are sorted by address
+            let address = (contribution.section, contribution.offset);
+            if let Some(last) = last {
+                assert!(last <= address);
+            }
+            last = Some(address);
+
+            // each module's first contribution is repeated in its module info
+            let module = &modules[contribution.module as usize];
+            if module.info().section.section == contribution.section &&
+                module.info().section.offset == contribution.offset {
+                assert_eq!(module.info().section.size, contribution.size);
+                assert_eq!(module.info().section.characteristics, contribution.characteristics);
+                assert_eq!(module.info().section.module, contribution.module);
+            }
+
+            count += 1;
+        }
+
+        assert_eq!(count, 6960);
+    });
+}
+
+#[test]
+fn contribution_index() {
+    setup(|dbi| {
+        let index = dbi.contribution_index().expect("contribution index");
+        assert_eq!(index.modules().len(), 194);
+
+        // every contribution can be found by its first and last byte
+        for contribution in index.contributions() {
+            let (module, found) = index.find(contribution.section, contribution.offset).expect("find first byte");
+            assert_eq!(found.section, contribution.section);
+            assert_eq!(found.offset, contribution.offset);
+            assert_eq!(module.info().stream, index.modules()[found.module as usize].info().stream);
+
+            let last_byte = contribution.offset + contribution.size - 1;
+            let (_, found) = index.find(contribution.section, last_byte).expect("find last byte");
+            assert!(found.contains(contribution.section, last_byte));
+        }
+
+        // nothing lives in section 0 or past the last contribution
+        assert!(index.find(0, 0).is_none());
+        let last = index.contributions().last().expect("last contribution");
+        assert!(index.find(last.section, last.offset + last.size).is_none());
+    });
+}
+
+#[test]
+fn find_main() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+
+    // find main() in the global symbol table
+    let symbol_table = pdb.global_symbols().expect("global symbols");
+    let mut main = None;
+    let mut symbols = symbol_table.iter();
+    while let Some(symbol) = symbols.next().expect("next symbol") {
+        if let Ok(pdb::SymbolData::PublicSymbol(data)) = symbol.parse() {
+            if symbol.name().expect("name").as_bytes() == b"main" {
+                main = Some(data);
+            }
+        }
+    }
+    let main = main.expect("main");
+
+    let dbi = pdb.debug_information().expect("debug information");
+    let index = dbi.contribution_index().expect("contribution index");
+    let (module, _) = index.find(main.segment, main.offset).expect("find main");
+    assert_eq!(module.module_name(), "c:\\Users\\User\\Desktop\\self\\foo.obj");
+
+    // the same module owns main's RVA
+    let sections = pdb.sections().expect("sections").expect("section headers present");
+    let rva = sections[main.segment as usize - 1].virtual_address + main.offset;
+    let (module, _) = index.find_rva(rva, &sections).expect("find main by RVA");
+    assert_eq!(module.module_name(), "c:\\Users\\User\\Desktop\\self\\foo.obj");
+    assert!(index.find_rva(0, &sections).is_none());
+}
+
+#[test]
+fn section_map() {
+    setup(|dbi| {
+        let section_map = dbi.section_map().expect("section map");
+        assert_eq!(section_map.segment_count(), 9);
+        assert_eq!(section_map.logical_segment_count(), 9);
+
+        let entries: Vec<pdb::SectionMapEntry> = section_map.collect().expect("collect section map");
+        assert_eq!(entries.len(), 9);
+
+        // the first segment is .text
+        let text = &entries[0];
+        assert!(text.flags.read() && text.flags.execute() && !text.flags.write());
+        assert!(text.flags.is_selector());
+        assert_eq!(text.frame, 1);
+        assert_eq!(text.frame_offset(0x1234), Some(0x1234));
+        assert_eq!(text.frame_offset(text.section_length), None);
+
+        // the following segments refer to subsequent sections
+        for (i, entry) in entries[..8].iter().enumerate() {
+            assert_eq!(entry.frame as usize, i + 1);
+        }
+
+        // the last one is the absolute pseudo-segment
+        assert!(entries[8].flags.is_absolute_address());
+    });
+}
+
+#[test]
+fn section_map_linear_addresses() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+    let sections = pdb.sections().expect("sections").expect("section headers present");
+
+    let dbi = pdb.debug_information().expect("debug information");
+    let entries: Vec<pdb::SectionMapEntry> = dbi.section_map().expect("section map")
+        .collect().expect("collect section map");
+
+    // .text starts at RVA 0x1000
+    assert_eq!(entries[0].linear_address(0x10, &sections), Some(0x1010));
+    assert_eq!(entries[0].linear_address(entries[0].section_length, &sections), None);
+    assert_eq!(entries[8].linear_address(0, &sections), None);
+}
+
+#[test]
+fn file_info() {
+    setup(|dbi| {
+        let file_info = dbi.file_info().expect("file info");
+        assert_eq!(file_info.module_count(), 194);
+
+        // the first module is foo.obj, compiled from foo.cpp
+        let files: Vec<String> = file_info.module_files(0).expect("module 0")
+            .map(|name| name.to_string().into_owned())
+            .collect().expect("collect files");
+        assert_eq!(files.len(), 5);
+        assert_eq!(files[0], "c:\\users\\user\\desktop\\self\\foo.cpp");
+        assert!(files.contains(&"c:\\program files (x86)\\windows kits\\10\\include\\10.0.14393.0\\ucrt\\stdio.h".to_string()));
+
+        assert!(file_info.module_files(194).is_none());
+
+        // iteration agrees with direct access
+        let mut count = 0;
+        let mut iter = file_info.iter();
+        while let Some(files) = iter.next().expect("next module") {
+            let expected = file_info.module_files(count).expect("module files");
+            assert_eq!(files.count().expect("count"), expected.count().expect("count"));
+            count += 1;
+        }
+        assert_eq!(count, 194);
+    });
+}
+
+#[test]
+fn extra_streams() {
+    setup(|dbi| {
+        let extra = dbi.extra_streams().expect("extra streams");
+
+        // this build was not post-processed and has no FPO data, so it only carries section headers
+        assert_eq!(extra.section_headers, Some(11));
+        assert_eq!(extra.stream(pdb::ExtraStreamKind::SectionHeaders), Some(11));
+        assert_eq!(extra.fpo, None);
+        assert_eq!(extra.omap_to_source, None);
+        assert_eq!(extra.omap_from_source, None);
+        assert_eq!(extra.original_section_headers, None);
+    });
+}
+
+#[test]
+fn extra_stream_by_kind() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+
+    // eight IMAGE_SECTION_HEADERs of 40 bytes each
+    let stream = pdb.extra_stream(pdb::ExtraStreamKind::SectionHeaders).expect("section headers");
+    assert_eq!(stream.expect("present").parse_buffer().len(), 8 * 40);
+
+    let stream = pdb.extra_stream(pdb::ExtraStreamKind::OmapFromSource).expect("omap from source");
+    assert!(stream.is_none());
+}
+
+#[test]
+fn sections() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+
+    let sections = pdb.sections().expect("sections").expect("section headers present");
+    let names: Vec<String> = sections.iter().map(|s| s.name().to_string().into_owned()).collect();
+    assert_eq!(names, vec![".text", ".rdata", ".data", ".pdata", ".idata", ".gfids", ".00cfg", ".reloc"]);
+
+    let text = &sections[0];
+    assert_eq!(text.virtual_address, 0x1000);
+    assert_eq!(text.virtual_size, 372660);
+    assert_eq!(text.characteristics, 0x6000_0020);
+
+    // sections are laid out in ascending order without overlapping
+    for pair in sections.windows(2) {
+        assert!(pair[0].virtual_address + pair[0].virtual_size <= pair[1].virtual_address);
+    }
+
+    // the executable was not rewritten after linking
+    assert!(pdb.original_sections().expect("original sections").is_none());
+}
+
+#[test]
+fn address_translator() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+    let translator = pdb.address_translator().expect("address translator");
+
+    // without OMAP, segment:offset is relative to the section's virtual address
+    assert!(!translator.is_transformed());
+    assert_eq!(translator.segment_offset_to_rva(1, 0x10), Some(0x1010));
+    assert_eq!(translator.segment_offset_to_rva(9, 0x10), None);
+
+    // public symbols map to an RVA and back, except the few in the absolute segment
+    let symbol_table = pdb.global_symbols().expect("global symbols");
+    let mut count = 0;
+    let mut absolute = 0;
+    let mut iter = symbol_table.iter();
+    while let Some(symbol) = iter.next().expect("next symbol") {
+        if let Ok(data @ pdb::SymbolData::PublicSymbol(_)) = symbol.parse() {
+            let (segment, offset) = data.segment_offset().expect("segment offset");
+            match translator.symbol_rva(&data) {
+                Some(rva) => {
+                    assert_eq!(translator.rva_to_segment_offset(rva), Some((segment, offset)));
+                    count += 1;
+                }
+                None => {
+                    assert_eq!(segment, 9);
+                    absolute += 1;
+                }
+            }
+        }
+    }
+    assert!(count > 2000);
+    assert_eq!(absolute, 3);
+}
+
+#[test]
+fn frame_tables() {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+
+    // x64 code unwinds using .pdata in the executable instead
+    assert!(pdb.fpo_table().expect("fpo table").is_none());
+    assert!(pdb.frame_table().expect("frame table").is_none());
+}
@@ -0,0 +1,59 @@
+extern crate pdb;
+
+fn line_index() -> pdb::LineIndex {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+    pdb.line_index().expect("line index")
+}
+
+#[test]
+fn find() {
+    let index = line_index();
+    // lines without any code are left out
+    assert_eq!(index.lines().len(), 19250);
+
+    // the first line of module 0 is foo.cpp:29 at 0001:000054f0, in the .text section at 0x1000
+    let line = index.find(0x64f0 + 3).expect("line");
+    assert_eq!(line.rva, 0x64f0);
+    assert_eq!(line.length, 14);
+    assert_eq!(line.line_start, 29);
+    assert_eq!(index.file_name(line.file).expect("file name").to_string(),
+               "c:\\users\\user\\desktop\\self\\foo.cpp");
+
+    // outside of any code
+    assert_eq!(index.find(0), None);
+
+    // lines are sorted and do not overlap
+    for pair in index.lines().windows(2) {
+        assert!(pair[0].rva + pair[0].length <= pair[1].rva, "{:?} overlaps {:?}", pair[0], pair[1]);
+    }
+}
+
+#[test]
+fn find_source() {
+    let index = line_index();
+    let file = index.find_file("c:\\users\\user\\desktop\\self\\foo.cpp".into()).expect("foo.cpp");
+
+    let lines = index.find_source(file, 29);
+    assert!(lines.iter().any(|line| line.rva == 0x64f0));
+    for line in lines {
+        assert_eq!(line.file, file);
+        assert_eq!(line.line_start, 29);
+        assert_eq!(index.find(line.rva), Some(line));
+    }
+
+    assert!(index.find_source(file, 100_000).is_empty());
+}
+
+#[test]
+fn module_without_module_info() {
+    // the "* Linker *" module of this PDB has no module info stream; see fixtures/synthetic
+    let file = std::fs::File::open("fixtures/synthetic/cross_module.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+    let index = pdb.line_index().expect("line index");
+
+    assert_eq!(index.lines().len(), 1);
+    let line = index.find(0x1010).expect("line");
+    assert_eq!(line.line_start, 7);
+    assert_eq!(index.file_name(line.file).expect("file name").to_string(), "a.cpp");
+}
@@ -0,0 +1,557 @@
+extern crate pdb;
+use pdb::FallibleIterator;
+use std::collections::HashMap;
+
+// symbol kinds, from cvinfo.h
+const S_END: u16 = 0x0006;
+const S_PROC_ID_END: u16 = 0x114f;
+const S_FRAMEPROC: u16 = 0x1012;
+
+fn setup<F>(func: F) where F: FnOnce(&mut pdb::PDB<std::fs::File>, &pdb::DebugInformation) {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+    let dbi = pdb.debug_information().expect("debug information");
+
+    func(&mut pdb, &dbi);
+}
+
+#[test]
+fn lines() {
+    setup(|pdb, dbi| {
+        let strings = pdb.string_table().expect("string table");
+        let module = dbi.modules().expect("modules").next().expect("next module").expect("module 0");
+        let info = pdb.module_info(&module).expect("module info");
+
+        let lines: Vec<pdb::LineInfo> = info.lines().expect("lines").collect().expect("collect lines");
+        assert_eq!(lines.len(), 23);
+
+        // the first function is defined at foo.cpp:29
+        assert_eq!(lines[0], pdb::LineInfo {
+            offset: 0x54f0,
+            segment: 1,
+            length: 14,
+            file_index: 0,
+            line_start: 29,
+            line_end: 29,
+            column_start: None,
+            column_end: None,
+            kind: pdb::LineInfoKind::Statement,
+        });
+        assert_eq!(info.file_name(lines[0].file_index, &strings).expect("file name").to_string(),
+                   "c:\\users\\user\\desktop\\self\\foo.cpp");
+
+        // inline functions from headers are attributed to their own files
+        let stdio = lines.iter().find(|line| line.line_start == 637).expect("stdio.h line");
+        assert_eq!(info.file_name(stdio.file_index, &strings).expect("file name").to_string(),
+                   "c:\\program files (x86)\\windows kits\\10\\include\\10.0.14393.0\\ucrt\\stdio.h");
+    });
+}
+
+#[test]
+fn all_lines() {
+    setup(|pdb, dbi| {
+        let strings = pdb.string_table().expect("string table");
+
+        let mut count = 0;
+        let mut modules = dbi.modules().expect("modules");
+        while let Some(module) = modules.next().expect("next module") {
+            let info = pdb.module_info(&module).expect("module info");
+            let mut lines = info.lines().expect("lines");
+            while let Some(line) = lines.next().expect("next line") {
+                // every line refers to a file we can name
+                info.file_name(line.file_index, &strings).expect("file name");
+                assert!(line.line_start <= line.line_end);
+                count += 1;
+            }
+        }
+
+        assert_eq!(count, 20184);
+    });
+}
+
+#[test]
+fn file_checksums() {
+    setup(|pdb, dbi| {
+        let strings = pdb.string_table().expect("string table");
+        let module = dbi.modules().expect("modules").next().expect("next module").expect("module 0");
+        let info = pdb.module_info(&module).expect("module info");
+
+        let files: Vec<pdb::FileChecksum> = info.file_checksums().expect("checksums").collect()
+            .expect("collect checksums");
+        assert_eq!(files.len(), 5);
+        assert_eq!(files[0].file_index, 0);
+        assert_eq!(strings.get(files[0].name).expect("name").to_string(),
+                   "c:\\users\\user\\desktop\\self\\foo.cpp");
+        assert_eq!(files[0].kind, pdb::FileChecksumKind::MD5);
+        assert_eq!(files[0].checksum, &[0x8b, 0xe8, 0xc7, 0x73, 0x44, 0xfa, 0xee, 0x23,
+                                        0x26, 0x02, 0x87, 0x94, 0x42, 0x8b, 0x8f, 0xc2]);
+
+        // lines refer to the same entries
+        let line = info.lines().expect("lines").next().expect("next line").expect("line");
+        assert_eq!(info.file_checksum(line.file_index).expect("checksum"), Some(files[0]));
+        assert_eq!(info.file_checksum(files[3].file_index).expect("checksum"), Some(files[3]));
+    });
+}
+
+#[test]
+fn all_file_checksums() {
+    setup(|pdb, dbi| {
+        let mut md5 = 0;
+        let mut sha256 = 0;
+        let mut modules = dbi.modules().expect("modules");
+        while let Some(module) = modules.next().expect("next module") {
+            let info = pdb.module_info(&module).expect("module info");
+            let mut files = info.file_checksums().expect("checksums");
+            while let Some(file) = files.next().expect("next checksum") {
+                match file.kind {
+                    pdb::FileChecksumKind::MD5 => { assert_eq!(file.checksum.len(), 16); md5 += 1; }
+                    pdb::FileChecksumKind::SHA256 => { assert_eq!(file.checksum.len(), 32); sha256 += 1; }
+                    other => panic!("unexpected checksum kind {:?}", other),
+                }
+            }
+        }
+
+        assert_eq!((md5, sha256), (547, 3318));
+    });
+}
+
+#[test]
+fn inlinee_lines() {
+    setup(|pdb, dbi| {
+        let strings = pdb.string_table().expect("string table");
+
+        // module 2 is the CRT's exe_main.obj, built with optimizations
+        let module = dbi.modules().expect("modules").nth(2).expect("nth module").expect("module 2");
+        let info = pdb.module_info(&module).expect("module info");
+
+        let inlinees: Vec<pdb::InlineeLine> = info.inlinee_lines().expect("inlinee lines").collect()
+            .expect("collect inlinee lines");
+        assert_eq!(inlinees[0], pdb::InlineeLine {
+            inlinee: 4218,
+            file_index: 408,
+            line: 378,
+            extra_files: vec![],
+        });
+        assert_eq!(info.file_name(inlinees[0].file_index, &strings).expect("file name").to_string(),
+                   "f:\\dd\\vctools\\crt\\vcstartup\\inc\\vcstartup_internal.h");
+
+        // module 0 is a debug build without inlining
+        let module = dbi.modules().expect("modules").next().expect("next module").expect("module 0");
+        let info = pdb.module_info(&module).expect("module info");
+        assert_eq!(info.inlinee_lines().expect("inlinee lines").count().expect("count"), 0);
+    });
+}
+
+#[test]
+fn all_inlinee_lines() {
+    setup(|pdb, dbi| {
+        let strings = pdb.string_table().expect("string table");
+
+        let mut count = 0;
+        let mut modules = dbi.modules().expect("modules");
+        while let Some(module) = modules.next().expect("next module") {
+            let info = pdb.module_info(&module).expect("module info");
+            let mut inlinees = info.inlinee_lines().expect("inlinee lines");
+            while let Some(inlinee) = inlinees.next().expect("next inlinee line") {
+                // every inlinee refers to a file we can name
+                info.file_name(inlinee.file_index, &strings).expect("file name");
+                count += 1;
+            }
+        }
+
+        assert_eq!(count, 1519);
+    });
+}
+
+#[test]
+fn cross_module_references() {
+    setup(|pdb, dbi| {
+        // this PDB uses global item indices throughout, so no module imports or exports any
+        let modules: Vec<pdb::Module> = dbi.modules().expect("modules").collect().expect("collect modules");
+        for module in &modules {
+            let info = pdb.module_info(module).expect("module info");
+            assert_eq!(info.cross_module_imports().expect("imports").count().expect("count"), 0);
+            assert_eq!(info.cross_module_exports().expect("exports").count().expect("count"), 0);
+
+            let reference = pdb::CrossModuleRef::from_index(0x8000_0000).expect("reference");
+            assert_eq!(info.resolve_cross_module_ref(reference).expect("resolve"), None);
+            assert!(pdb.cross_module_target(&info, reference, &modules).expect("target").is_none());
+            assert_eq!(info.global_index(0x1000).expect("global index"), None);
+        }
+    });
+}
+
+#[test]
+fn cross_module_target() {
+    // a synthetic PDB in which a.obj imports item 0x1003 of b.obj; see fixtures/synthetic
+    let file = std::fs::File::open("fixtures/synthetic/cross_module.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+    let dbi = pdb.debug_information().expect("debug information");
+    let modules: Vec<pdb::Module> = dbi.modules().expect("modules").collect().expect("collect modules");
+    assert_eq!(modules.len(), 3);
+
+    let info = pdb.module_info(&modules[0]).expect("module info");
+    let reference = pdb::CrossModuleRef::from_index(0x8000_0000).expect("reference");
+    let (module, local_index) = pdb.cross_module_target(&info, reference, &modules)
+        .expect("target")
+        .expect("target present");
+    assert_eq!(module.module_name(), "b.obj");
+    assert_eq!(local_index, 0x1003);
+
+    let target = pdb.module_info(module).expect("target module info");
+    assert_eq!(target.global_index(local_index).expect("global index"), Some(0x1234));
+
+    // a.obj only imports a single item from a single module
+    let reference = pdb::CrossModuleRef::from_index(0x8000_0001).expect("reference");
+    assert!(pdb.cross_module_target(&info, reference, &modules).expect("target").is_none());
+    let reference = pdb::CrossModuleRef::from_index(0x8010_0000).expect("reference");
+    assert!(pdb.cross_module_target(&info, reference, &modules).expect("target").is_none());
+}
+
+#[test]
+fn procedures() {
+    setup(|pdb, dbi| {
+        let translator = pdb.address_translator().expect("address translator");
+
+        let mut count = 0;
+        let mut main = None;
+        let mut modules = dbi.modules().expect("modules");
+        while let Some(module) = modules.next().expect("next module") {
+            let info = pdb.module_info(&module).expect("module info");
+            let mut symbols = info.symbols().expect("symbols");
+            while let Some(symbol) = symbols.next().expect("next symbol") {
+                let procedure = match symbol.parse() {
+                    Ok(pdb::SymbolData::Procedure(procedure)) => procedure,
+                    Ok(_) | Err(pdb::Error::UnimplementedSymbolKind(_)) => continue,
+                    Err(e) => panic!("failed to parse {:?}: {}", symbol, e),
+                };
+
+                assert!(procedure.end > procedure.parent);
+                assert!(procedure.dbg_start_offset <= procedure.dbg_end_offset);
+                assert!(procedure.dbg_end_offset <= procedure.len);
+                translator.symbol_rva(&pdb::SymbolData::Procedure(procedure)).expect("procedure RVA");
+
+                if symbol.name().expect("name").to_string() == "main" {
+                    assert!(main.is_none());
+                    main = Some(procedure);
+                }
+                count += 1;
+            }
+        }
+
+        assert_eq!(count, 2768);
+
+        let main = main.expect("main");
+        assert!(main.global);
+        assert_eq!((main.segment, main.offset, main.len), (1, 0x5560, 32));
+    });
+}
+
+#[test]
+fn scoped_symbols() {
+    setup(|pdb, dbi| {
+        let mut modules = dbi.modules().expect("modules");
+        while let Some(module) = modules.next().expect("next module") {
+            let info = pdb.module_info(&module).expect("module info");
+            let mut ends = Vec::new();
+            let mut symbols = info.scoped_symbols().expect("scoped symbols");
+            while let Some(scoped) = symbols.next().expect("next symbol") {
+                assert_eq!(info.symbol_at(scoped.offset).expect("symbol at offset"), scoped.symbol);
+
+                if let Ok(pdb::SymbolData::Procedure(procedure)) = scoped.symbol.parse() {
+                    // procedures are closed at the same depth by the symbol at their end offset
+                    ends.push((procedure.end, scoped.depth, scoped.procedure));
+                }
+                if ends.first().map(|end| end.0) == Some(scoped.offset) {
+                    let (_, depth, procedure) = ends.remove(0);
+                    assert!(scoped.symbol.raw_kind() == S_END || scoped.symbol.raw_kind() == S_PROC_ID_END);
+                    assert_eq!((scoped.depth, scoped.procedure), (depth, procedure));
+                }
+            }
+            assert!(ends.is_empty());
+
+            // the very first symbol comes after the signature
+            match info.symbol_at(0) {
+                Err(pdb::Error::UnexpectedEof) => (),
+                _ => panic!("expected UnexpectedEof"),
+            }
+        }
+    });
+}
+
+#[test]
+fn procedure_references() {
+    setup(|pdb, dbi| {
+        let modules: Vec<pdb::Module> = dbi.modules().expect("modules").collect().expect("collect modules");
+        let globals = pdb.global_symbols().expect("global symbols");
+
+        let mut count = 0;
+        let mut symbols = globals.iter();
+        while let Some(symbol) = symbols.next().expect("next symbol") {
+            if let Ok(pdb::SymbolData::ProcedureReference(reference)) = symbol.parse() {
+                // module numbers are one-based
+                let module = &modules[reference.module as usize - 1];
+                let info = pdb.module_info(module).expect("module info");
+                let procedure = info.symbol_at(reference.symbol_index).expect("symbol at offset");
+                assert_eq!(procedure.name().expect("procedure name"), symbol.name().expect("name"));
+                count += 1;
+            }
+        }
+
+        assert!(count > 2000);
+    });
+}
+
+#[test]
+fn local_variables() {
+    setup(|pdb, dbi| {
+        let mut register_relative = 0;
+        let mut stack_pointer = 0;
+        let mut locals = 0;
+        let mut modules = dbi.modules().expect("modules");
+        while let Some(module) = modules.next().expect("next module") {
+            let info = pdb.module_info(&module).expect("module info");
+            let mut symbols = info.symbols().expect("symbols");
+            while let Some(symbol) = symbols.next().expect("next symbol") {
+                match symbol.parse() {
+                    Ok(pdb::SymbolData::RegisterRelative(variable)) => {
+                        let register = variable.register.amd64().expect("x64 register");
+                        assert!(register == pdb::AMD64Register::RSP || register == pdb::AMD64Register::RBP);
+                        if register == pdb::AMD64Register::RSP {
+                            stack_pointer += 1;
+                        }
+                        register_relative += 1;
+                    }
+                    Ok(pdb::SymbolData::Local(_)) => locals += 1,
+                    Ok(_) | Err(pdb::Error::UnimplementedSymbolKind(_)) => (),
+                    Err(e) => panic!("failed to parse {:?}: {}", symbol, e),
+                }
+            }
+        }
+
+        assert_eq!((register_relative, stack_pointer), (5151, 4941));
+        assert_eq!(locals, 8284);
+    });
+}
+
+#[test]
+fn variable_locations() {
+    setup(|pdb, dbi| {
+        let mut ranges = 0;
+        let mut modules = dbi.modules().expect("modules");
+        while let Some(module) = modules.next().expect("next module") {
+            let info = pdb.module_info(&module).expect("module info");
+            let mut symbols = info.symbols().expect("symbols");
+            while let Some(symbol) = symbols.next().expect("next symbol") {
+                match symbol.parse() {
+                    Ok(pdb::SymbolData::DefRangeRegister(_)) |
+                    Ok(pdb::SymbolData::DefRangeFramePointerRelative(_)) |
+                    Ok(pdb::SymbolData::DefRangeSubfieldRegister(_)) |
+                    Ok(pdb::SymbolData::DefRangeFramePointerRelativeFullScope(_)) |
+                    Ok(pdb::SymbolData::DefRangeRegisterRelative(_)) => ranges += 1,
+                    Ok(_) | Err(pdb::Error::UnimplementedSymbolKind(_)) => (),
+                    Err(e) => panic!("failed to parse {:?}: {}", symbol, e),
+                }
+            }
+        }
+
+        assert_eq!(ranges, 11385 + 351 + 204 + 1002 + 364);
+
+        // `has_cctor` lives in SIL for a few instructions, and on the stack everywhere else
+        let module = dbi.modules().expect("modules").nth(2).expect("nth").expect("module 2");
+        let info = pdb.module_info(&module).expect("module info");
+        let local = info.symbol_at(480).expect("symbol at");
+        assert_eq!(local.name().expect("name").to_string(), "has_cctor");
+
+        let sil = pdb::VariableLocation::Register(pdb::Register(pdb::AMD64Register::SIL as u16));
+        assert_eq!(info.variable_location(480, 1, 0x5807).expect("location"), sil);
+        assert_eq!(info.variable_location(480, 1, 0x5810).expect("location"), pdb::VariableLocation::FramePointerRelative(32));
+
+        match info.variable_location(500, 1, 0x5807) {
+            Err(pdb::Error::UnexpectedSymbolKind(0x1141)) => (),
+            other => panic!("unexpected {:?}", other),
+        }
+    });
+}
+
+#[test]
+fn inline_sites() {
+    setup(|pdb, dbi| {
+        let mut sites = 0;
+        let mut modules = dbi.modules().expect("modules");
+        while let Some(module) = modules.next().expect("next module") {
+            let info = pdb.module_info(&module).expect("module info");
+            let inlinees: HashMap<pdb::ItemIndex, pdb::InlineeLine> = info.inlinee_lines().expect("inlinee lines")
+                .map(|line| (line.inlinee, line))
+                .collect()
+                .expect("collect");
+
+            let mut symbols = info.scoped_symbols().expect("scoped symbols");
+            while let Some(scoped) = symbols.next().expect("next symbol") {
+                let site = match scoped.symbol.parse() {
+                    Ok(pdb::SymbolData::InlineSite(site)) => site,
+                    Ok(_) | Err(pdb::Error::UnimplementedSymbolKind(_)) => continue,
+                    Err(e) => panic!("failed to parse {:?}: {}", scoped.symbol, e),
+                };
+
+                let offset = scoped.procedure.expect("inline site outside of a procedure");
+                let procedure = match info.symbol_at(offset).expect("symbol at").parse() {
+                    Ok(pdb::SymbolData::Procedure(procedure)) => procedure,
+                    other => panic!("unexpected {:?}", other),
+                };
+
+                let inlinee = &inlinees[&site.inlinee];
+                let mut lines = site.lines(&procedure, inlinee);
+                while let Some(line) = lines.next().expect("next line") {
+                    assert_eq!(line.segment, procedure.segment);
+                    assert!(line.offset >= procedure.offset);
+                    assert!(line.offset + line.length <= procedure.offset + procedure.len);
+                }
+                sites += 1;
+            }
+        }
+
+        assert_eq!(sites, 5988);
+
+        // a single inline site in module 2, whose code is split in two
+        let module = dbi.modules().expect("modules").nth(2).expect("nth").expect("module 2");
+        let info = pdb.module_info(&module).expect("module info");
+        let site = match info.symbol_at(2400).expect("symbol at").parse() {
+            Ok(pdb::SymbolData::InlineSite(site)) => site,
+            other => panic!("unexpected {:?}", other),
+        };
+        let procedure = match info.symbol_at(site.parent).expect("symbol at").parse() {
+            Ok(pdb::SymbolData::Procedure(procedure)) => procedure,
+            other => panic!("unexpected {:?}", other),
+        };
+        let inlinee = info.inlinee_lines().expect("inlinee lines")
+            .find(|line| line.inlinee == site.inlinee)
+            .expect("find")
+            .expect("inlinee line");
+
+        let lines: Vec<_> = site.lines(&procedure, &inlinee)
+            .map(|line| (line.offset, line.length, line.line_start - inlinee.line))
+            .collect().expect("lines");
+        assert_eq!(lines, vec![(0x5a24, 5, 0), (0x5a2d, 5, 2)]);
+    });
+}
+
+#[test]
+fn compile_info() {
+    setup(|pdb, dbi| {
+        let mut modules = dbi.modules().expect("modules");
+        let module = modules.next().expect("next module").expect("module 0");
+        let info = pdb.module_info(&module).expect("module info");
+        let compile_info = info.compile_info().expect("compile info").expect("S_COMPILE3");
+
+        assert_eq!(compile_info.object_name.map(|name| name.to_string().into_owned()),
+                   Some("c:\\Users\\User\\Desktop\\self\\foo.obj".to_string()));
+        assert_eq!(compile_info.compiler_name.to_string(), "Microsoft (R) Optimizing Compiler");
+        assert_eq!(compile_info.language, pdb::SourceLanguage::Cpp);
+        assert_eq!(compile_info.cpu_type, pdb::CPUType::X64);
+        assert_eq!(compile_info.frontend_version.to_string(), "19.0.24215.1");
+        assert_eq!(compile_info.backend_version, compile_info.frontend_version);
+        assert!(compile_info.flags.security_checks);
+        assert!(compile_info.flags.hot_patch);
+        assert!(!compile_info.flags.pgo);
+        assert!(!compile_info.flags.link_time_codegen);
+        assert_eq!(compile_info.build_info, Some(4125));
+        assert!(compile_info.environment.is_empty());
+
+        // the linker records its command line
+        let module = dbi.modules().expect("modules").last().expect("last").expect("linker module");
+        let info = pdb.module_info(&module).expect("module info");
+        let compile_info = info.compile_info().expect("compile info").expect("S_COMPILE3");
+        assert_eq!(compile_info.object_name.map(|name| name.to_string().into_owned()),
+                   Some("* Linker *".to_string()));
+        assert_eq!(compile_info.language, pdb::SourceLanguage::Link);
+        let command = compile_info.environment.iter().find(|(key, _)| key == "cmd").expect("cmd");
+        assert!(command.1.contains("/debug:full"));
+
+        // every module in the fixture was built for x64
+        let mut count = 0;
+        let mut modules = dbi.modules().expect("modules");
+        while let Some(module) = modules.next().expect("next module") {
+            let info = pdb.module_info(&module).expect("module info");
+            let compile_info = info.compile_info().expect("compile info").expect("S_COMPILE2 or S_COMPILE3");
+            assert_eq!(compile_info.cpu_type, pdb::CPUType::X64);
+            count += 1;
+        }
+        assert_eq!(count, 194);
+    });
+}
+
+#[test]
+fn frame_procedures() {
+    setup(|pdb, dbi| {
+        let mut frames = 0;
+        let mut modules = dbi.modules().expect("modules");
+        while let Some(module) = modules.next().expect("next module") {
+            let info = pdb.module_info(&module).expect("module info");
+            let mut symbols = info.scoped_symbols().expect("scoped symbols");
+            while let Some(scoped) = symbols.next().expect("next symbol") {
+                if let Ok(pdb::SymbolData::Procedure(_)) = scoped.symbol.parse() {
+                    if info.frame_procedure(scoped.offset).expect("frame procedure").is_some() {
+                        frames += 1;
+                    }
+                }
+            }
+        }
+
+        assert_eq!(frames, 2753);
+
+        // main, in module 0
+        let module = dbi.modules().expect("modules").next().expect("next module").expect("module 0");
+        let info = pdb.module_info(&module).expect("module info");
+        assert_eq!(info.symbol_at(664).expect("symbol at").name().expect("name").to_string(), "main");
+
+        let frame = info.frame_procedure(664).expect("frame procedure").expect("S_FRAMEPROC");
+        assert_eq!(frame.frame_size, 40);
+        assert!(!frame.flags.has_alloca);
+        assert!(!frame.flags.has_seh);
+
+        let cpu_type = info.compile_info().expect("compile info").expect("S_COMPILE3").cpu_type;
+        let rsp = pdb::Register(pdb::AMD64Register::RSP as u16);
+        assert_eq!(frame.local_base_register(cpu_type), Some(rsp));
+        assert_eq!(frame.param_base_register(cpu_type), Some(rsp));
+
+        match info.frame_procedure(708) {
+            Err(pdb::Error::UnexpectedSymbolKind(S_FRAMEPROC)) => (),
+            other => panic!("unexpected {:?}", other),
+        }
+    });
+}
+
+#[test]
+fn thunks_and_trampolines() {
+    setup(|pdb, dbi| {
+        let mut counts = HashMap::new();
+        let mut modules = dbi.modules().expect("modules");
+        while let Some(module) = modules.next().expect("next module") {
+            let info = pdb.module_info(&module).expect("module info");
+            let mut symbols = info.symbols().expect("symbols");
+            while let Some(symbol) = symbols.next().expect("next symbol") {
+                let kind = match symbol.parse() {
+                    Ok(pdb::SymbolData::Thunk(data)) => {
+                        assert_eq!(data.kind, pdb::ThunkKind::NoType);
+                        "thunk"
+                    }
+                    Ok(pdb::SymbolData::Trampoline(data)) => {
+                        assert_eq!(data.kind, pdb::TrampolineKind::Incremental);
+                        "trampoline"
+                    }
+                    Ok(pdb::SymbolData::Label(_)) => "label",
+                    Ok(pdb::SymbolData::Block(_)) => "block",
+                    _ => continue,
+                };
+                assert!(symbol.parse().expect("parse").segment_offset().is_some());
+                *counts.entry(kind).or_insert(0) += 1;
+            }
+        }
+
+        assert_eq!(counts.get("thunk"), Some(&83));
+        assert_eq!(counts.get("trampoline"), Some(&2173));
+        assert_eq!(counts.get("label"), Some(&340));
+        assert_eq!(counts.get("block"), Some(&1));
+    });
+}
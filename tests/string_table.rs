@@ -0,0 +1,47 @@
+extern crate pdb;
+
+fn setup<F>(func: F) where F: FnOnce(&pdb::StringTable) {
+    let file = std::fs::File::open("fixtures/self/foo.pdb").expect("opening file");
+    let mut pdb = pdb::PDB::open(file).expect("opening pdb");
+    let strings = pdb.string_table().expect("string table");
+
+    func(&strings);
+}
+
+#[test]
+fn header() {
+    setup(|strings| {
+        assert_eq!(strings.hash_version(), pdb::StringTableHashVersion::V1);
+        assert_eq!(strings.len(), 377);
+    });
+}
+
+#[test]
+fn get() {
+    setup(|strings| {
+        assert_eq!(strings.get(0).expect("empty string").as_bytes(), b"");
+        assert_eq!(strings.get(146).expect("string").as_bytes(), &b"c:\\users\\user\\desktop\\self\\foo.cpp"[..]);
+
+        match strings.get(0x7fff_ffff) {
+            Err(pdb::Error::UnexpectedEof) => (),
+            _ => panic!("expected UnexpectedEof"),
+        }
+    });
+}
+
+#[test]
+fn find() {
+    setup(|strings| {
+        let names = [
+            "c:\\users\\user\\desktop\\self\\foo.cpp",
+            "c:\\program files (x86)\\microsoft visual studio 14.0\\vc\\include\\vadefs.h",
+        ];
+
+        for name in &names {
+            let offset = strings.find((*name).into()).expect("find").expect("name present");
+            assert_eq!(strings.get(offset).expect("get").to_string(), *name);
+        }
+
+        assert_eq!(strings.find("c:\\no\\such\\file.cpp".into()).expect("find"), None);
+    });
+}